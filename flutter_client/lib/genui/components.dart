@@ -3,6 +3,7 @@ import 'package:flutter_riverpod/flutter_riverpod.dart';
 
 import '../services/llm_models.dart';
 import '../services/model_repository.dart';
+import '../services/preferences.dart';
 import '../services/tts_service.dart';
 
 class ModelSelectorCard extends ConsumerWidget {
@@ -168,8 +169,7 @@ class SpeedSlider extends ConsumerWidget {
           max: 3.0,
           divisions: 25,
           label: config.rate.toStringAsFixed(2),
-          onChanged: (value) =>
-              ref.read(ttsConfigProvider.notifier).updateRate(value),
+          onChanged: (value) => ref.read(ttsServiceProvider).setRate(value),
         ),
       ],
     );
@@ -181,11 +181,13 @@ class ThemeToggle extends ConsumerWidget {
 
   @override
   Widget build(BuildContext context, WidgetRef ref) {
-    final isDark = Theme.of(context).brightness == Brightness.dark;
+    final theme = ref.watch(appThemeProvider);
     return SwitchListTile(
-      value: isDark,
+      value: theme != AppTheme.light,
       title: const Text('Dark Mode'),
-      onChanged: (_) {},
+      onChanged: (dark) => ref
+          .read(appThemeProvider.notifier)
+          .select(dark ? AppTheme.dark : AppTheme.light),
     );
   }
 }
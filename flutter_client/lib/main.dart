@@ -8,6 +8,8 @@ import 'package:genui/genui.dart';
 import 'package:tts_flutter_client/api.dart' as bridge;
 import 'package:tts_flutter_client/frb_generated.dart';
 
+import 'services/focus_pause.dart';
+import 'services/preferences.dart';
 import 'ui/editor_screen.dart';
 
 Future<void> main() async {
@@ -17,14 +19,18 @@ Future<void> main() async {
   runApp(const ProviderScope(child: TtsApp()));
 }
 
-class TtsApp extends StatelessWidget {
+class TtsApp extends ConsumerWidget {
   const TtsApp({super.key});
 
   @override
-  Widget build(BuildContext context) {
+  Widget build(BuildContext context, WidgetRef ref) {
+    final theme = ref.watch(appThemeProvider);
+    ref.watch(focusPauseObserverProvider);
     return MaterialApp(
       title: 'TTS Beast',
-      theme: ThemeData.dark(),
+      theme: ThemeData.light(),
+      darkTheme: ThemeData.dark(),
+      themeMode: theme.mode,
       home: const EditorScreen(),
     );
   }
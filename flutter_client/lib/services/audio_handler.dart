@@ -31,6 +31,7 @@ class TtsAudioHandler extends BaseAudioHandler with SeekHandler {
     Uint8List pcmBytes,
     int sampleRate, {
     required String cacheDirPath,
+    String? title,
   }) async {
     if (pcmBytes.isEmpty || sampleRate <= 0) {
       return Duration.zero;
@@ -51,10 +52,13 @@ class TtsAudioHandler extends BaseAudioHandler with SeekHandler {
     final duration = Duration(
       milliseconds: (pcmBytes.length / 2 / sampleRate * 1000).round(),
     );
+    // audio_service relays this MediaItem to the OS media session
+    // (Android notification, macOS Now Playing, Windows SMTC), so
+    // lock-screen and media widgets show what is being read.
     _currentItem = MediaItem(
       id: uri.toString(),
       album: 'Generated Speech',
-      title: 'TTS Playback',
+      title: title == null || title.isEmpty ? 'TTS Playback' : title,
       duration: duration,
     );
     mediaItem.add(_currentItem);
@@ -83,6 +87,33 @@ class TtsAudioHandler extends BaseAudioHandler with SeekHandler {
   @override
   Future<void> seek(Duration position) => _player.seek(position);
 
+  /// Applied live: changing the speed mid-sentence takes effect on the
+  /// audio already playing, not just the next utterance.
+  @override
+  Future<void> setSpeed(double speed) => _player.setSpeed(speed);
+
+  /// Headset/media-key button presses. A single press toggles based on
+  /// the actual player state, so a stale notification can't get the
+  /// toggle out of sync; double/triple presses map to the 15s skips
+  /// since there is no track list to jump between.
+  @override
+  Future<void> click([MediaButton button = MediaButton.media]) {
+    switch (button) {
+      case MediaButton.media:
+        return _player.playing ? pause() : play();
+      case MediaButton.next:
+        return fastForward();
+      case MediaButton.previous:
+        return rewind();
+    }
+  }
+
+  @override
+  Future<void> skipToNext() => fastForward();
+
+  @override
+  Future<void> skipToPrevious() => rewind();
+
   @override
   Future<void> fastForward() =>
       _player.seek(_player.position + const Duration(seconds: 15));
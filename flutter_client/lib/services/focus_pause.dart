@@ -0,0 +1,71 @@
+import 'package:flutter/material.dart';
+import 'package:flutter_riverpod/flutter_riverpod.dart';
+
+import 'audio_handler.dart';
+import 'preferences.dart';
+
+/// Opt-in: pause speech when the app loses focus (alt-tab, lock screen)
+/// and resume when it comes back. Off by default — background listening
+/// is the normal mode.
+final pauseOnFocusLossProvider =
+    StateNotifierProvider<PauseOnFocusLossNotifier, bool>((ref) {
+  return PauseOnFocusLossNotifier();
+});
+
+class PauseOnFocusLossNotifier extends StateNotifier<bool> {
+  PauseOnFocusLossNotifier() : super(false) {
+    _hydrate();
+  }
+
+  static const _key = 'pause_on_focus_loss';
+
+  Future<void> _hydrate() async {
+    final value = await readPreference(_key);
+    if (value is bool && mounted) {
+      state = value;
+    }
+  }
+
+  Future<void> toggle() async {
+    state = !state;
+    await writePreference(_key, state);
+  }
+}
+
+/// Keeps the lifecycle observer registered for the app's lifetime.
+/// Watch this once at the root widget to activate the behavior.
+final focusPauseObserverProvider = Provider<FocusPauseObserver>((ref) {
+  final observer = FocusPauseObserver(ref);
+  WidgetsBinding.instance.addObserver(observer);
+  ref.onDispose(() => WidgetsBinding.instance.removeObserver(observer));
+  return observer;
+});
+
+class FocusPauseObserver with WidgetsBindingObserver {
+  FocusPauseObserver(this._ref);
+
+  final Ref _ref;
+
+  /// Set only when *we* paused. A manually paused session never has it,
+  /// so regaining focus won't surprise-resume something the user
+  /// stopped on purpose.
+  bool _autoPaused = false;
+
+  @override
+  Future<void> didChangeAppLifecycleState(AppLifecycleState state) async {
+    if (!_ref.read(pauseOnFocusLossProvider)) {
+      _autoPaused = false;
+      return;
+    }
+    final handler = await _ref.read(audioHandlerProvider);
+    if (state == AppLifecycleState.resumed) {
+      if (_autoPaused) {
+        _autoPaused = false;
+        await handler.play();
+      }
+    } else if (handler.playbackState.value.playing) {
+      _autoPaused = true;
+      await handler.pause();
+    }
+  }
+}
@@ -0,0 +1,181 @@
+import 'dart:convert';
+import 'dart:io';
+
+import 'package:collection/collection.dart';
+import 'package:flutter/material.dart';
+import 'package:flutter_riverpod/flutter_riverpod.dart';
+import 'package:path_provider/path_provider.dart';
+
+Future<File> _prefsFile() async {
+  final dir = await getApplicationSupportDirectory();
+  return File('${dir.path}/prefs.json');
+}
+
+/// Read one value from the JSON preferences file; `null` when absent or
+/// the file is unreadable (defaults apply).
+Future<Object?> readPreference(String key) async {
+  try {
+    final file = await _prefsFile();
+    if (!await file.exists()) {
+      return null;
+    }
+    final map = jsonDecode(await file.readAsString());
+    return map is Map ? map[key] : null;
+  } catch (_) {
+    return null;
+  }
+}
+
+/// Best-effort write of one value, preserving the other keys.
+Future<void> writePreference(String key, Object? value) async {
+  try {
+    final file = await _prefsFile();
+    var map = <String, dynamic>{};
+    if (await file.exists()) {
+      final existing = jsonDecode(await file.readAsString());
+      if (existing is Map) {
+        map = existing.cast<String, dynamic>();
+      }
+    }
+    map[key] = value;
+    await file.writeAsString(jsonEncode(map));
+  } catch (_) {
+    // The in-memory state already changed; losing the write only costs
+    // the preference on next launch.
+  }
+}
+
+/// Whether the player view follows the spoken word. Persisted across
+/// launches.
+final followModeProvider =
+    StateNotifierProvider<FollowModeNotifier, bool>((ref) {
+  return FollowModeNotifier();
+});
+
+class FollowModeNotifier extends StateNotifier<bool> {
+  FollowModeNotifier() : super(true) {
+    _hydrate();
+  }
+
+  static const _key = 'follow_active_word';
+
+  Future<void> _hydrate() async {
+    final value = await readPreference(_key);
+    if (value is bool && mounted) {
+      state = value;
+    }
+  }
+
+  Future<void> toggle() async {
+    state = !state;
+    await writePreference(_key, state);
+  }
+}
+
+/// UI theme. Dark is the historical default; system defers to the OS.
+enum AppTheme {
+  system(ThemeMode.system),
+  light(ThemeMode.light),
+  dark(ThemeMode.dark);
+
+  const AppTheme(this.mode);
+
+  final ThemeMode mode;
+}
+
+/// Selected theme, persisted and applied live at the `MaterialApp` root.
+final appThemeProvider =
+    StateNotifierProvider<AppThemeNotifier, AppTheme>((ref) {
+  return AppThemeNotifier();
+});
+
+class AppThemeNotifier extends StateNotifier<AppTheme> {
+  AppThemeNotifier() : super(AppTheme.dark) {
+    _hydrate();
+  }
+
+  static const _key = 'theme';
+
+  Future<void> _hydrate() async {
+    final value = await readPreference(_key);
+    final match =
+        AppTheme.values.firstWhereOrNull((theme) => theme.name == value);
+    if (match != null && mounted) {
+      state = match;
+    }
+  }
+
+  Future<void> select(AppTheme theme) async {
+    state = theme;
+    await writePreference(_key, theme.name);
+  }
+}
+
+/// Reading text size in logical pixels, applied live to the player view.
+final readerFontSizeProvider =
+    StateNotifierProvider<ReaderFontSizeNotifier, double>((ref) {
+  return ReaderFontSizeNotifier();
+});
+
+class ReaderFontSizeNotifier extends StateNotifier<double> {
+  ReaderFontSizeNotifier() : super(defaultSize) {
+    _hydrate();
+  }
+
+  static const _key = 'font_size';
+  static const defaultSize = 16.0;
+  static const minSize = 12.0;
+  static const maxSize = 32.0;
+
+  Future<void> _hydrate() async {
+    final value = await readPreference(_key);
+    if (value is num && mounted) {
+      state = value.toDouble().clamp(minSize, maxSize);
+    }
+  }
+
+  Future<void> select(double size) async {
+    state = size.clamp(minSize, maxSize);
+    await writePreference(_key, state);
+  }
+}
+
+/// Maximum reading column width, so long lines stay readable on wide
+/// windows.
+enum ColumnWidth {
+  narrow(560),
+  medium(720),
+  wide(960);
+
+  const ColumnWidth(this.maxWidth);
+
+  final double maxWidth;
+}
+
+/// Selected column width preset, persisted like follow-mode.
+final columnWidthProvider =
+    StateNotifierProvider<ColumnWidthNotifier, ColumnWidth>((ref) {
+  return ColumnWidthNotifier();
+});
+
+class ColumnWidthNotifier extends StateNotifier<ColumnWidth> {
+  ColumnWidthNotifier() : super(ColumnWidth.medium) {
+    _hydrate();
+  }
+
+  static const _key = 'column_width';
+
+  Future<void> _hydrate() async {
+    final value = await readPreference(_key);
+    final match =
+        ColumnWidth.values.firstWhereOrNull((preset) => preset.name == value);
+    if (match != null && mounted) {
+      state = match;
+    }
+  }
+
+  Future<void> select(ColumnWidth preset) async {
+    state = preset;
+    await writePreference(_key, preset.name);
+  }
+}
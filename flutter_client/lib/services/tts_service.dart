@@ -10,6 +10,7 @@ import '../services/llm_models.dart';
 import '../services/model_repository.dart';
 import '../services/text_analysis.dart';
 import 'audio_handler.dart';
+import 'preferences.dart';
 
 final ttsConfigProvider =
     StateNotifierProvider<TtsConfigNotifier, TtsConfig>((ref) {
@@ -17,6 +18,8 @@ final ttsConfigProvider =
 });
 
 final currentWordIndexProvider = StateProvider<int>((ref) => 0);
+final playbackDurationProvider =
+    StateProvider<Duration>((ref) => Duration.zero);
 final wordBoundariesProvider =
     StateProvider<List<TextWordBoundary>>((ref) => const []);
 final wordCuesProvider = StateProvider<List<WordCue>>((ref) => const []);
@@ -59,7 +62,18 @@ class TtsConfigNotifier extends StateNotifier<TtsConfig> {
               );
             }(),
           ),
-        );
+        ) {
+    _hydrateRate();
+  }
+
+  static const _rateKey = 'default_rate';
+
+  Future<void> _hydrateRate() async {
+    final value = await readPreference(_rateKey);
+    if (value is num && mounted) {
+      state = state.copyWith(rate: value.toDouble().clamp(0.5, 3.0));
+    }
+  }
 
   void selectVoice(VoiceSelection selection) {
     state = state.copyWith(voice: selection);
@@ -71,6 +85,7 @@ class TtsConfigNotifier extends StateNotifier<TtsConfig> {
 
   void updateRate(double value) {
     state = state.copyWith(rate: value);
+    writePreference(_rateKey, value);
   }
 
   void selectLlmModel(String model) {
@@ -115,6 +130,15 @@ class TtsService {
   final Ref _ref;
   StreamSubscription<Duration>? _positionSub;
 
+  /// Change the speech rate and apply it to whatever is playing right
+  /// now, so dragging the slider is audible mid-sentence instead of
+  /// from the next utterance.
+  Future<void> setRate(double value) async {
+    _ref.read(ttsConfigProvider.notifier).updateRate(value);
+    final handler = await _ref.read(audioHandlerProvider);
+    await handler.setSpeed(value);
+  }
+
   Future<void> speak(String rawText) async {
     final text = rawText.trim();
     if (text.isEmpty) {
@@ -186,7 +210,10 @@ class TtsService {
       pcmBytes,
       resolvedRate,
       cacheDirPath: cacheDir.path,
+      title: _nowPlayingTitle(text),
     );
+    await audioHandler.setSpeed(_ref.read(ttsConfigProvider).rate);
+    _ref.read(playbackDurationProvider.notifier).state = duration;
     final boundaries = computeWordBoundaries(text);
     _ref.read(wordBoundariesProvider.notifier).state = boundaries;
     final cues = buildWordCues(boundaries.length, duration);
@@ -195,6 +222,17 @@ class TtsService {
     _attachTimeline(audioHandler, cues);
   }
 
+  /// First few words of the text, used as the OS now-playing title so
+  /// media widgets identify what is being read.
+  String _nowPlayingTitle(String text) {
+    const maxWords = 8;
+    final words = text.split(RegExp(r'\s+'));
+    if (words.length <= maxWords) {
+      return words.join(' ');
+    }
+    return '${words.take(maxWords).join(' ')}…';
+  }
+
   void _attachTimeline(TtsAudioHandler handler, List<WordCue> cues) {
     _positionSub?.cancel();
     if (cues.isEmpty) {
@@ -5,7 +5,9 @@ import '../genui/components.dart';
 import '../genui/panel.dart';
 import '../services/text_analysis.dart';
 import '../services/tts_service.dart';
+import 'mini_player.dart';
 import 'player_screen.dart';
+import 'settings_screen.dart';
 
 class EditorScreen extends ConsumerStatefulWidget {
   const EditorScreen({super.key});
@@ -39,6 +41,13 @@ class _EditorScreenState extends ConsumerState<EditorScreen> {
             icon: const Icon(Icons.smart_toy),
             onPressed: () => _showGenUiSheet(),
           ),
+          IconButton(
+            icon: const Icon(Icons.settings),
+            tooltip: 'Settings',
+            onPressed: () => Navigator.of(context).push(
+              MaterialPageRoute(builder: (_) => const SettingsScreen()),
+            ),
+          ),
         ],
       ),
       body: SafeArea(
@@ -78,6 +87,7 @@ class _EditorScreenState extends ConsumerState<EditorScreen> {
           ),
         ),
       ),
+      bottomNavigationBar: const MiniPlayerBar(),
     );
   }
 
@@ -0,0 +1,109 @@
+import 'package:flutter/material.dart';
+import 'package:flutter_riverpod/flutter_riverpod.dart';
+
+import '../services/audio_handler.dart';
+import '../services/tts_service.dart';
+
+/// Compact transport bar meant to sit at the bottom of any screen:
+/// play/pause, ±15s skips, and a seek slider. Hidden until something has
+/// been synthesized, so fresh launches don't show dead controls.
+///
+/// The audio handler is app-scoped, so playback deliberately continues
+/// after the player screen is popped; the stop button here is the
+/// explicit way to end background listening.
+class MiniPlayerBar extends ConsumerStatefulWidget {
+  const MiniPlayerBar({super.key});
+
+  @override
+  ConsumerState<MiniPlayerBar> createState() => _MiniPlayerBarState();
+}
+
+class _MiniPlayerBarState extends ConsumerState<MiniPlayerBar> {
+  TtsAudioHandler? _handler;
+
+  @override
+  void initState() {
+    super.initState();
+    ref.read(audioHandlerProvider).then((handler) {
+      if (mounted) {
+        setState(() => _handler = handler);
+      }
+    });
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final handler = _handler;
+    final total = ref.watch(playbackDurationProvider);
+    if (handler == null || total == Duration.zero) {
+      return const SizedBox.shrink();
+    }
+    return Material(
+      elevation: 8,
+      color: Theme.of(context).colorScheme.surfaceContainerHighest,
+      child: SafeArea(
+        top: false,
+        child: Padding(
+          padding: const EdgeInsets.symmetric(horizontal: 8),
+          child: Row(
+            children: [
+              IconButton(
+                icon: const Icon(Icons.fast_rewind),
+                tooltip: 'Back 15 seconds',
+                onPressed: handler.rewind,
+              ),
+              StreamBuilder<bool>(
+                stream: handler.playbackState
+                    .map((state) => state.playing)
+                    .distinct(),
+                initialData: handler.playbackState.value.playing,
+                builder: (context, snapshot) {
+                  final playing = snapshot.data ?? false;
+                  return IconButton(
+                    icon: Icon(playing ? Icons.pause : Icons.play_arrow),
+                    tooltip: playing ? 'Pause' : 'Play',
+                    onPressed: playing ? handler.pause : handler.play,
+                  );
+                },
+              ),
+              IconButton(
+                icon: const Icon(Icons.fast_forward),
+                tooltip: 'Forward 15 seconds',
+                onPressed: handler.fastForward,
+              ),
+              IconButton(
+                icon: const Icon(Icons.stop),
+                tooltip: 'Stop playback',
+                onPressed: () async {
+                  await handler.stop();
+                  // Hides the bar and resets the highlight.
+                  ref.read(playbackDurationProvider.notifier).state =
+                      Duration.zero;
+                  ref.read(currentWordIndexProvider.notifier).state = 0;
+                },
+              ),
+              Expanded(
+                child: StreamBuilder<Duration>(
+                  stream: handler.positionStream(),
+                  initialData: Duration.zero,
+                  builder: (context, snapshot) {
+                    final position = snapshot.data ?? Duration.zero;
+                    final max = total.inMilliseconds.toDouble();
+                    return Slider(
+                      value: position.inMilliseconds
+                          .clamp(0, total.inMilliseconds)
+                          .toDouble(),
+                      max: max > 0 ? max : 1,
+                      onChanged: (value) => handler
+                          .seek(Duration(milliseconds: value.round())),
+                    );
+                  },
+                ),
+              ),
+            ],
+          ),
+        ),
+      ),
+    );
+  }
+}
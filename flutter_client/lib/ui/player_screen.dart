@@ -1,7 +1,10 @@
 import 'package:flutter/material.dart';
+import 'package:flutter/services.dart';
 import 'package:flutter_riverpod/flutter_riverpod.dart';
 
+import '../services/audio_handler.dart';
 import '../services/model_repository.dart';
+import '../services/preferences.dart';
 import '../services/text_analysis.dart';
 import '../services/tts_service.dart';
 
@@ -15,9 +18,19 @@ class PlayerScreen extends ConsumerStatefulWidget {
 }
 
 class _PlayerScreenState extends ConsumerState<PlayerScreen> {
+  TtsAudioHandler? _audioHandler;
+  // Bumped by "jump to current" to request a one-off scroll while
+  // follow-mode is off.
+  int _jumpToken = 0;
+
   @override
   void initState() {
     super.initState();
+    ref.read(audioHandlerProvider).then((handler) {
+      if (mounted) {
+        setState(() => _audioHandler = handler);
+      }
+    });
     Future.microtask(() async {
       try {
         await ref.read(ttsServiceProvider).speak(widget.text);
@@ -30,6 +43,27 @@ class _PlayerScreenState extends ConsumerState<PlayerScreen> {
     });
   }
 
+  /// Space (and the media play/pause key) toggles playback. The handler
+  /// only sees keys when no text field has focus, so typing elsewhere in
+  /// the app never triggers it.
+  KeyEventResult _handleKey(FocusNode node, KeyEvent event) {
+    if (event is! KeyDownEvent) {
+      return KeyEventResult.ignored;
+    }
+    final isToggle = event.logicalKey == LogicalKeyboardKey.space ||
+        event.logicalKey == LogicalKeyboardKey.mediaPlayPause;
+    final handler = _audioHandler;
+    if (!isToggle || handler == null) {
+      return KeyEventResult.ignored;
+    }
+    if (handler.playbackState.value.playing) {
+      handler.pause();
+    } else {
+      handler.play();
+    }
+    return KeyEventResult.handled;
+  }
+
   @override
   Widget build(BuildContext context) {
     final wordIndex = ref.watch(currentWordIndexProvider);
@@ -38,62 +72,223 @@ class _PlayerScreenState extends ConsumerState<PlayerScreen> {
         boundaries.isEmpty ? computeWordBoundaries(widget.text) : boundaries;
     final config = ref.watch(ttsConfigProvider);
     final usesPiper = config.voice.backend == TtsEngineBackend.piper;
+    final followActive = ref.watch(followModeProvider);
+    final columnWidth = ref.watch(columnWidthProvider);
     return Scaffold(
-      appBar: AppBar(title: const Text('Streaming Playback')),
-      body: Padding(
-        padding: const EdgeInsets.all(16),
-        child: Column(
-          crossAxisAlignment: CrossAxisAlignment.start,
-          children: [
-            Align(
-              alignment: Alignment.centerLeft,
-              child: Chip(
-                avatar: Icon(
-                  usesPiper ? Icons.graphic_eq : Icons.bolt,
-                  color: Theme.of(context).colorScheme.onSecondary,
-                ),
-                backgroundColor: usesPiper
-                    ? Theme.of(context).colorScheme.secondary
-                    : Theme.of(context).colorScheme.tertiaryContainer,
-                label: Text(
-                  usesPiper
-                      ? 'Real voice: ${config.voice.displayName}'
-                      : 'Synth preview voice',
+      appBar: AppBar(
+        title: const Text('Streaming Playback'),
+        actions: [
+          IconButton(
+            icon: Icon(followActive ? Icons.my_location : Icons.location_disabled),
+            tooltip: followActive
+                ? 'Stop following the spoken word'
+                : 'Follow the spoken word',
+            onPressed: () => ref.read(followModeProvider.notifier).toggle(),
+          ),
+          PopupMenuButton<ColumnWidth>(
+            icon: const Icon(Icons.format_line_spacing),
+            tooltip: 'Reading column width',
+            initialValue: columnWidth,
+            onSelected: (preset) =>
+                ref.read(columnWidthProvider.notifier).select(preset),
+            itemBuilder: (context) => [
+              for (final preset in ColumnWidth.values)
+                PopupMenuItem(
+                  value: preset,
+                  child: Text(
+                    '${preset.name[0].toUpperCase()}${preset.name.substring(1)}',
+                  ),
                 ),
-              ),
+            ],
+          ),
+        ],
+      ),
+      floatingActionButton: followActive
+          ? null
+          : FloatingActionButton.small(
+              tooltip: 'Jump to the spoken word',
+              onPressed: () => setState(() => _jumpToken++),
+              child: const Icon(Icons.center_focus_strong),
             ),
-            const Text('Live Highlight'),
-            const SizedBox(height: 12),
-            Expanded(
-              child: _HighlightedText(
-                text: widget.text,
-                activeIndex: wordIndex,
-                boundaries: effectiveBoundaries,
+      body: Focus(
+        autofocus: true,
+        onKeyEvent: _handleKey,
+        child: Center(
+          child: ConstrainedBox(
+            constraints: BoxConstraints(maxWidth: columnWidth.maxWidth),
+            child: Padding(
+              padding: const EdgeInsets.all(16),
+              child: Column(
+                crossAxisAlignment: CrossAxisAlignment.start,
+                children: [
+                  Align(
+                    alignment: Alignment.centerLeft,
+                    child: Chip(
+                      avatar: Icon(
+                        usesPiper ? Icons.graphic_eq : Icons.bolt,
+                        color: Theme.of(context).colorScheme.onSecondary,
+                      ),
+                      backgroundColor: usesPiper
+                          ? Theme.of(context).colorScheme.secondary
+                          : Theme.of(context).colorScheme.tertiaryContainer,
+                      label: Text(
+                        usesPiper
+                            ? 'Real voice: ${config.voice.displayName}'
+                            : 'Synth preview voice',
+                      ),
+                    ),
+                  ),
+                  const Text('Live Highlight'),
+                  const SizedBox(height: 12),
+                  Expanded(
+                    child: _HighlightedText(
+                      text: widget.text,
+                      activeIndex: wordIndex,
+                      boundaries: effectiveBoundaries,
+                      followActive: followActive,
+                      jumpToken: _jumpToken,
+                      fontSize: ref.watch(readerFontSizeProvider),
+                    ),
+                  ),
+                  if (_audioHandler != null)
+                    _PlaybackClock(
+                      handler: _audioHandler!,
+                      total: ref.watch(playbackDurationProvider),
+                    ),
+                ],
               ),
             ),
-          ],
+          ),
         ),
       ),
     );
   }
 }
 
-class _HighlightedText extends StatelessWidget {
+/// "12:34 / 41:02" readout under the text. The position stream is
+/// quantized to whole seconds and deduplicated, so the row repaints about
+/// once per second instead of on every player tick.
+class _PlaybackClock extends StatelessWidget {
+  const _PlaybackClock({required this.handler, required this.total});
+
+  final TtsAudioHandler handler;
+  final Duration total;
+
+  static String _format(Duration duration) {
+    final seconds = duration.inSeconds;
+    final hours = seconds ~/ 3600;
+    final minutes = (seconds % 3600) ~/ 60;
+    final secs = (seconds % 60).toString().padLeft(2, '0');
+    return hours > 0
+        ? '$hours:${minutes.toString().padLeft(2, '0')}:$secs'
+        : '$minutes:$secs';
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    return StreamBuilder<Duration>(
+      stream: handler
+          .positionStream()
+          .map((position) => Duration(seconds: position.inSeconds))
+          .distinct(),
+      initialData: Duration.zero,
+      builder: (context, snapshot) {
+        final position = snapshot.data ?? Duration.zero;
+        return Padding(
+          padding: const EdgeInsets.only(top: 8),
+          child: Text(
+            '${_format(position)} / ${_format(total)}',
+            style: Theme.of(context).textTheme.bodySmall,
+          ),
+        );
+      },
+    );
+  }
+}
+
+class _HighlightedText extends StatefulWidget {
   const _HighlightedText({
     required this.text,
     required this.activeIndex,
     required this.boundaries,
+    this.followActive = true,
+    this.jumpToken = 0,
+    this.fontSize = ReaderFontSizeNotifier.defaultSize,
   });
 
   final String text;
   final int activeIndex;
   final List<TextWordBoundary> boundaries;
 
+  /// Reading text size from settings, applied live.
+  final double fontSize;
+
+  /// Keep the highlighted word visible as playback advances. Off means
+  /// the view stays put for reading ahead.
+  final bool followActive;
+
+  /// Incremented by the parent to request a single scroll to the active
+  /// word even when follow-mode is off.
+  final int jumpToken;
+
+  @override
+  State<_HighlightedText> createState() => _HighlightedTextState();
+}
+
+class _HighlightedTextState extends State<_HighlightedText> {
+  final ScrollController _controller = ScrollController();
+
+  @override
+  void didUpdateWidget(_HighlightedText oldWidget) {
+    super.didUpdateWidget(oldWidget);
+    final follow =
+        widget.followActive && widget.activeIndex != oldWidget.activeIndex;
+    if (follow || widget.jumpToken != oldWidget.jumpToken) {
+      WidgetsBinding.instance.addPostFrameCallback((_) => _scrollToActive());
+    }
+  }
+
+  @override
+  void dispose() {
+    _controller.dispose();
+    super.dispose();
+  }
+
+  /// Estimate the active word's offset from its share of the text and
+  /// scroll only when it leaves the comfortable middle band of the
+  /// viewport, so small highlight steps don't jitter the view.
+  void _scrollToActive() {
+    if (!_controller.hasClients || widget.boundaries.isEmpty) {
+      return;
+    }
+    final position = _controller.position;
+    final content = position.maxScrollExtent + position.viewportDimension;
+    final target = content * widget.activeIndex / widget.boundaries.length;
+    final top = position.pixels + position.viewportDimension * 0.15;
+    final bottom = position.pixels + position.viewportDimension * 0.75;
+    if (target >= top && target <= bottom) {
+      return;
+    }
+    _controller.animateTo(
+      (target - position.viewportDimension / 3)
+          .clamp(0.0, position.maxScrollExtent),
+      duration: const Duration(milliseconds: 200),
+      curve: Curves.easeOut,
+    );
+  }
+
   @override
   Widget build(BuildContext context) {
+    final text = widget.text;
+    final boundaries = widget.boundaries;
+    final baseStyle = Theme.of(context)
+        .textTheme
+        .bodyLarge
+        ?.copyWith(fontSize: widget.fontSize);
     if (boundaries.isEmpty) {
       return SingleChildScrollView(
-        child: Text(text, style: Theme.of(context).textTheme.bodyLarge),
+        controller: _controller,
+        child: Text(text, style: baseStyle),
       );
     }
     final spans = <TextSpan>[];
@@ -104,7 +299,7 @@ class _HighlightedText extends StatelessWidget {
         spans.add(TextSpan(text: text.substring(cursor, boundary.start)));
       }
       final wordText = text.substring(boundary.start, boundary.end);
-      final isActive = boundary.index == activeIndex;
+      final isActive = boundary.index == widget.activeIndex;
       spans.add(
         TextSpan(
           text: wordText,
@@ -123,9 +318,10 @@ class _HighlightedText extends StatelessWidget {
       spans.add(TextSpan(text: text.substring(cursor)));
     }
     return SingleChildScrollView(
+      controller: _controller,
       child: RichText(
         text: TextSpan(
-          style: Theme.of(context).textTheme.bodyLarge,
+          style: baseStyle,
           children: spans,
         ),
       ),
@@ -0,0 +1,127 @@
+import 'package:flutter/material.dart';
+import 'package:flutter_riverpod/flutter_riverpod.dart';
+
+import '../services/focus_pause.dart';
+import '../services/preferences.dart';
+import '../services/tts_service.dart';
+
+/// Central settings screen. Every control writes straight through its
+/// notifier, so changes apply live (theme, font size, rate) and persist
+/// immediately — there is no save button to forget.
+class SettingsScreen extends ConsumerWidget {
+  const SettingsScreen({super.key});
+
+  @override
+  Widget build(BuildContext context, WidgetRef ref) {
+    final theme = ref.watch(appThemeProvider);
+    final fontSize = ref.watch(readerFontSizeProvider);
+    final followActive = ref.watch(followModeProvider);
+    final columnWidth = ref.watch(columnWidthProvider);
+    final config = ref.watch(ttsConfigProvider);
+    return Scaffold(
+      appBar: AppBar(title: const Text('Settings')),
+      body: ListView(
+        children: [
+          const _SectionHeader('Appearance'),
+          ListTile(
+            title: const Text('Theme'),
+            trailing: DropdownButton<AppTheme>(
+              value: theme,
+              onChanged: (value) {
+                if (value != null) {
+                  ref.read(appThemeProvider.notifier).select(value);
+                }
+              },
+              items: [
+                for (final choice in AppTheme.values)
+                  DropdownMenuItem(
+                    value: choice,
+                    child: Text(_capitalize(choice.name)),
+                  ),
+              ],
+            ),
+          ),
+          ListTile(
+            title: const Text('Reading text size'),
+            subtitle: Slider(
+              value: fontSize,
+              min: ReaderFontSizeNotifier.minSize,
+              max: ReaderFontSizeNotifier.maxSize,
+              divisions: 20,
+              label: fontSize.toStringAsFixed(0),
+              onChanged: (value) =>
+                  ref.read(readerFontSizeProvider.notifier).select(value),
+            ),
+          ),
+          ListTile(
+            title: const Text('Reading column width'),
+            trailing: DropdownButton<ColumnWidth>(
+              value: columnWidth,
+              onChanged: (value) {
+                if (value != null) {
+                  ref.read(columnWidthProvider.notifier).select(value);
+                }
+              },
+              items: [
+                for (final preset in ColumnWidth.values)
+                  DropdownMenuItem(
+                    value: preset,
+                    child: Text(_capitalize(preset.name)),
+                  ),
+              ],
+            ),
+          ),
+          const _SectionHeader('Playback'),
+          SwitchListTile(
+            title: const Text('Follow the spoken word'),
+            value: followActive,
+            onChanged: (_) => ref.read(followModeProvider.notifier).toggle(),
+          ),
+          SwitchListTile(
+            title: const Text('Pause when the app loses focus'),
+            subtitle: const Text(
+              'Auto-paused speech resumes when you come back',
+            ),
+            value: ref.watch(pauseOnFocusLossProvider),
+            onChanged: (_) =>
+                ref.read(pauseOnFocusLossProvider.notifier).toggle(),
+          ),
+          ListTile(
+            title: const Text('Default speech rate'),
+            subtitle: Slider(
+              value: config.rate,
+              min: 0.5,
+              max: 3.0,
+              divisions: 25,
+              label: config.rate.toStringAsFixed(2),
+              onChanged: (value) =>
+                  ref.read(ttsServiceProvider).setRate(value),
+            ),
+          ),
+        ],
+      ),
+    );
+  }
+
+  static String _capitalize(String value) =>
+      '${value[0].toUpperCase()}${value.substring(1)}';
+}
+
+class _SectionHeader extends StatelessWidget {
+  const _SectionHeader(this.label);
+
+  final String label;
+
+  @override
+  Widget build(BuildContext context) {
+    return Padding(
+      padding: const EdgeInsets.fromLTRB(16, 16, 16, 4),
+      child: Text(
+        label,
+        style: Theme.of(context).textTheme.titleSmall?.copyWith(
+              color: Theme.of(context).colorScheme.primary,
+            ),
+      ),
+    );
+  }
+}
@@ -0,0 +1,25 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_core::engine::{chunk_audio_samples, EngineRegistryHandle};
+
+fn bench_mock_synthesis(c: &mut Criterion) {
+    let registry = EngineRegistryHandle::default();
+    let engine = registry.mock_engine("bench");
+    let mut group = c.benchmark_group("mock_synthesis");
+    for words in [8usize, 64, 256] {
+        let text = vec!["lorem"; words].join(" ");
+        group.bench_with_input(BenchmarkId::from_parameter(words), &text, |b, text| {
+            b.iter(|| engine.synthesize(text).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn bench_chunking(c: &mut Criterion) {
+    let samples: Vec<i16> = (0..16_000 * 30).map(|n| (n % 512) as i16).collect();
+    c.bench_function("chunk_audio_samples_30s", |b| {
+        b.iter(|| chunk_audio_samples(samples.clone(), 16_000, 4096));
+    });
+}
+
+criterion_group!(benches, bench_mock_synthesis, bench_chunking);
+criterion_main!(benches);
@@ -11,7 +11,7 @@ use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use crate::engine::{AudioFrame, EngineRegistryHandle, RegistryError, TTSEngine};
+use crate::engine::{AudioFrame, CancelToken, EngineRegistryHandle, RegistryError, TTSEngine};
 
 #[cfg(feature = "bridge")]
 use flutter_rust_bridge::frb;
@@ -79,6 +79,12 @@ pub struct PiperBackendConfig {
 
 static ENGINE_REGISTRY: Lazy<RwLock<Option<EngineRegistryHandle>>> =
     Lazy::new(|| RwLock::new(None));
+static ACTIVE_STREAM_CANCEL: Lazy<RwLock<Option<CancelToken>>> = Lazy::new(|| RwLock::new(None));
+static ACTIVE_PREVIEW_CANCEL: Lazy<RwLock<Option<CancelToken>>> = Lazy::new(|| RwLock::new(None));
+
+/// Fixed phrase used by the voice picker's preview action.
+pub const VOICE_PREVIEW_PHRASE: &str =
+    "The quick brown fox jumps over the lazy dog.";
 static TRACING_INIT: Once = Once::new();
 
 pub fn init_registry(handle: EngineRegistryHandle) {
@@ -135,6 +141,29 @@ pub fn current_state() -> TtsState {
     }
 }
 
+/// Warm up the requested engine on a background thread so the first real
+/// sentence after opening a reader is snappy. Runs `warmup` plus a tiny
+/// throwaway synthesis; failures are logged and otherwise ignored.
+#[cfg_attr(feature = "bridge", frb)]
+pub fn preload_engine(request: EngineRequest) {
+    let maybe_registry = ENGINE_REGISTRY.read().clone();
+    let Some(handle) = maybe_registry else {
+        return;
+    };
+    let backend = request.backend;
+    thread::spawn(move || match resolve_engine(&handle, &backend) {
+        Ok(engine) => {
+            let started = std::time::Instant::now();
+            engine.warmup();
+            let _ = engine.synthesize(".");
+            info!(elapsed_ms = started.elapsed().as_millis() as u64, "engine preloaded");
+        }
+        Err(err) => {
+            info!(%err, "engine preload skipped");
+        }
+    });
+}
+
 #[cfg_attr(feature = "bridge", frb)]
 pub fn stream_audio(text: String, request: EngineRequest, sink: StreamSink<AudioChunk>) {
     let maybe_registry = ENGINE_REGISTRY.read().clone();
@@ -145,10 +174,17 @@ pub fn stream_audio(text: String, request: EngineRequest, sink: StreamSink<Audio
     let backend = request.backend.clone();
     let model_path = backend_model_path(&backend).to_string();
 
+    // Supersede any stream still running; frames are dropped between
+    // sends, so the old thread winds down promptly.
+    let cancel = CancelToken::new();
+    if let Some(previous) = ACTIVE_STREAM_CANCEL.write().replace(cancel.clone()) {
+        previous.cancel();
+    }
+
     info!(%model_path, "spawning synthesis thread");
     thread::spawn(move || match resolve_engine(&handle, &backend) {
         Ok(engine) => match engine.synthesize(&text) {
-            Ok(frames) => dispatch_frames(frames, sink),
+            Ok(frames) => dispatch_frames(frames, sink, &cancel),
             Err(err) => {
                 let _ = sink.add_error(anyhow!(err).to_string());
             }
@@ -159,8 +195,50 @@ pub fn stream_audio(text: String, request: EngineRequest, sink: StreamSink<Audio
     });
 }
 
-fn dispatch_frames(frames: Vec<AudioFrame>, sink: StreamSink<AudioChunk>) {
+/// Synthesize a short fixed phrase with the requested engine so the
+/// voice picker can play a sample, independent of the reading stream.
+/// Starting a new preview cancels the previous one.
+#[cfg_attr(feature = "bridge", frb)]
+pub fn preview_voice(request: EngineRequest, sink: StreamSink<AudioChunk>) {
+    let maybe_registry = ENGINE_REGISTRY.read().clone();
+    let Some(handle) = maybe_registry else {
+        let _ = sink.add_error(anyhow!("engine registry not initialized").to_string());
+        return;
+    };
+    let backend = request.backend;
+
+    let cancel = CancelToken::new();
+    if let Some(previous) = ACTIVE_PREVIEW_CANCEL.write().replace(cancel.clone()) {
+        previous.cancel();
+    }
+
+    thread::spawn(move || match resolve_engine(&handle, &backend) {
+        Ok(engine) => match engine.synthesize(VOICE_PREVIEW_PHRASE) {
+            Ok(frames) => dispatch_frames(frames, sink, &cancel),
+            Err(err) => {
+                let _ = sink.add_error(anyhow!(err).to_string());
+            }
+        },
+        Err(err) => {
+            let _ = sink.add_error(anyhow!(err).to_string());
+        }
+    });
+}
+
+/// Cancel the active `stream_audio` stream, if any. Returns once the
+/// flag is set; the synthesis thread stops at its next frame boundary.
+#[cfg_attr(feature = "bridge", frb)]
+pub fn stop_streaming() {
+    if let Some(cancel) = ACTIVE_STREAM_CANCEL.write().take() {
+        cancel.cancel();
+    }
+}
+
+fn dispatch_frames(frames: Vec<AudioFrame>, sink: StreamSink<AudioChunk>, cancel: &CancelToken) {
     for frame in frames {
+        if cancel.is_cancelled() {
+            return;
+        }
         let chunk = AudioChunk {
             pcm: frame.samples,
             sample_rate: frame.sample_rate,
@@ -1,5 +1,16 @@
 pub mod buffer_generator;
+pub mod read_along;
+pub mod resume_cache;
+pub mod sync_import;
 pub mod sync_map;
+pub mod tts_cache;
 
 pub use buffer_generator::float_to_pcm_i16;
+pub use read_along::{
+    record_listening_position, resume_audio_position, select_reading_mode, ReadAlongSession,
+    ReadingMode,
+};
+pub use resume_cache::{ResumeCache, ResumeKey};
+pub use sync_import::import_sync_file;
 pub use sync_map::{SyncMap, SyncPoint};
+pub use tts_cache::{TtsCache, TtsCacheKey};
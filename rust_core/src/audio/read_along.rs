@@ -0,0 +1,198 @@
+//! Read-along mode: playing a book's own audio while highlighting the
+//! matching text through a [`SyncMap`], instead of the TTS loop.
+
+use std::time::Duration;
+
+use super::SyncMap;
+use crate::library::Ebook;
+
+/// How a book should be presented when opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadingMode {
+    /// Dual-media with a sync map: play the audio, highlight the text.
+    ReadAlong,
+    /// Text with no (usable) audio alignment: synthesize with TTS.
+    Tts,
+    /// Audio without text: plain playback.
+    AudioOnly,
+}
+
+/// Pick the mode for `book`. Read-along needs both media plus an
+/// alignment; without one we fall back to TTS so the book still reads.
+pub fn select_reading_mode(book: &Ebook, has_sync_map: bool) -> ReadingMode {
+    if book.has_audio() && book.has_text() && has_sync_map {
+        ReadingMode::ReadAlong
+    } else if book.has_text() {
+        ReadingMode::Tts
+    } else {
+        ReadingMode::AudioOnly
+    }
+}
+
+/// Highlight state for one read-along chapter. Playback position ticks
+/// drive the highlight forward; text clicks translate back to seeks.
+pub struct ReadAlongSession {
+    map: SyncMap,
+    active_index: Option<usize>,
+}
+
+impl ReadAlongSession {
+    pub fn new(map: SyncMap) -> Self {
+        Self {
+            map,
+            active_index: None,
+        }
+    }
+
+    /// Advance to the playback position; returns the newly active text
+    /// index only when it changed, so callers repaint at span boundaries
+    /// rather than on every tick.
+    pub fn on_position(&mut self, position: Duration) -> Option<usize> {
+        let index = self.map.resolve_index(position)?;
+        if self.active_index == Some(index) {
+            return None;
+        }
+        self.active_index = Some(index);
+        Some(index)
+    }
+
+    /// Seek target for a clicked text position.
+    pub fn seek_for_text(&self, text_index: usize) -> Option<Duration> {
+        self.map.resolve_timestamp(text_index)
+    }
+
+    pub fn active_index(&self) -> Option<usize> {
+        self.active_index
+    }
+}
+
+/// Record where listening stopped as the book's unified text position,
+/// so switching to reading resumes at the same spot. The audio position
+/// is translated through the sync map; positions before the first sync
+/// point save as the chapter start.
+pub fn record_listening_position(
+    db: &crate::persistence::Database,
+    book_id: &crate::library::EbookId,
+    chapter: usize,
+    map: &SyncMap,
+    position: Duration,
+) -> Result<(), crate::persistence::PersistenceError> {
+    let sentence = map.resolve_index(position).unwrap_or(0);
+    db.save_progress(
+        book_id,
+        crate::persistence::ReaderPosition {
+            chapter,
+            sentence,
+            word: 0,
+        },
+    )
+}
+
+/// Where audio playback should resume to match the saved reading
+/// position. `None` when there is no saved position for this chapter or
+/// the map can't place it.
+pub fn resume_audio_position(
+    db: &crate::persistence::Database,
+    book_id: &crate::library::EbookId,
+    chapter: usize,
+    map: &SyncMap,
+) -> Result<Option<Duration>, crate::persistence::PersistenceError> {
+    let Some(position) = db.load_progress(book_id)? else {
+        return Ok(None);
+    };
+    if position.chapter != chapter {
+        return Ok(None);
+    }
+    Ok(map.resolve_timestamp(position.sentence))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::{AudioChapter, EbookId, TextContent, TextFormat};
+    use std::path::PathBuf;
+
+    fn book(audio: bool, text: bool) -> Ebook {
+        Ebook {
+            id: EbookId("book".into()),
+            title: "Book".into(),
+            author: None,
+            description: None,
+            path: PathBuf::from("book"),
+            audio_chapters: if audio {
+                vec![AudioChapter {
+                    chapter_index: 0,
+                    title: "01".into(),
+                    file: PathBuf::from("01.mp3"),
+                    track_number: None,
+                    duration: None,
+                }]
+            } else {
+                Vec::new()
+            },
+            text: text.then(|| TextContent {
+                file: PathBuf::from("book.txt"),
+                format: TextFormat::PlainText,
+                continuation: Vec::new(),
+            }),
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        }
+    }
+
+    #[test]
+    fn mode_prefers_read_along_and_falls_back_to_tts() {
+        assert_eq!(
+            select_reading_mode(&book(true, true), true),
+            ReadingMode::ReadAlong
+        );
+        assert_eq!(select_reading_mode(&book(true, true), false), ReadingMode::Tts);
+        assert_eq!(select_reading_mode(&book(false, true), false), ReadingMode::Tts);
+        assert_eq!(
+            select_reading_mode(&book(true, false), false),
+            ReadingMode::AudioOnly
+        );
+    }
+
+    #[test]
+    fn listening_and_reading_positions_hand_off_both_ways() {
+        use crate::persistence::Database;
+
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("dual".into());
+        let mut map = SyncMap::default();
+        map.push_point(0, Duration::from_secs(0));
+        map.push_point(4, Duration::from_secs(30));
+        map.push_point(9, Duration::from_secs(75));
+
+        // Pause audio mid-chapter: the text position lands on sentence 4.
+        record_listening_position(&db, &id, 2, &map, Duration::from_secs(40)).unwrap();
+        let saved = db.load_progress(&id).unwrap().unwrap();
+        assert_eq!((saved.chapter, saved.sentence), (2, 4));
+
+        // Coming back to listen resumes at that sentence's timestamp.
+        assert_eq!(
+            resume_audio_position(&db, &id, 2, &map).unwrap(),
+            Some(Duration::from_secs(30))
+        );
+        // A different chapter doesn't inherit the position.
+        assert_eq!(resume_audio_position(&db, &id, 3, &map).unwrap(), None);
+    }
+
+    #[test]
+    fn position_ticks_report_only_span_changes() {
+        let mut map = SyncMap::default();
+        map.push_point(0, Duration::from_millis(0));
+        map.push_point(8, Duration::from_millis(500));
+
+        let mut session = ReadAlongSession::new(map);
+        assert_eq!(session.on_position(Duration::from_millis(10)), Some(0));
+        // Same span: no repaint.
+        assert_eq!(session.on_position(Duration::from_millis(400)), None);
+        assert_eq!(session.on_position(Duration::from_millis(600)), Some(8));
+        assert_eq!(session.seek_for_text(9), Some(Duration::from_millis(500)));
+        assert_eq!(session.active_index(), Some(8));
+    }
+}
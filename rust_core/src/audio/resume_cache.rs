@@ -0,0 +1,164 @@
+//! One-slot cache of the last synthesized sentence, so resuming a paused
+//! sentence replays it instead of hitting the engine again.
+//!
+//! Nothing wires this cache into playback yet: the current Flutter
+//! client plays each sentence through `just_audio`, which pauses and
+//! resumes in place on its own and never hands the PCM back to the
+//! core. The whole module — and [`ResumeCache::record_pause`] /
+//! [`ResumeCache::resume_from_pause`] in particular — is a staged
+//! integration point for the planned Rust-driven playback loop, where
+//! the core owns the sink and must capture the elapsed offset itself on
+//! pause and slice the buffer on resume. Hook it up when that loop
+//! lands.
+
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+/// What the cached buffer was synthesized with; resume only reuses it
+/// when all of these still match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResumeKey {
+    pub engine: String,
+    pub voice: String,
+    /// Rate in thousandths, matching [`super::TtsCacheKey`].
+    pub rate_milli: u32,
+    pub sentence_index: usize,
+}
+
+struct CachedSentence {
+    key: ResumeKey,
+    samples: Vec<i16>,
+    sample_rate: u32,
+    /// How far the sink had played when the user paused, recorded so
+    /// resume can continue mid-word instead of from the word boundary.
+    pause_offset: Option<Duration>,
+}
+
+/// Session-scoped cache holding at most the current sentence's audio.
+/// Any change of voice, rate, or sentence index invalidates it.
+#[derive(Default)]
+pub struct ResumeCache {
+    slot: Mutex<Option<CachedSentence>>,
+}
+
+impl ResumeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember the sentence that just played (or was paused).
+    pub fn store(&self, key: ResumeKey, samples: Vec<i16>, sample_rate: u32) {
+        *self.slot.lock() = Some(CachedSentence {
+            key,
+            samples,
+            sample_rate,
+            pause_offset: None,
+        });
+    }
+
+    /// Record the sink's playback offset at pause time. A no-op when
+    /// nothing is cached (pause raced a sentence change).
+    pub fn record_pause(&self, elapsed: Duration) {
+        if let Some(cached) = self.slot.lock().as_mut() {
+            cached.pause_offset = Some(elapsed);
+        }
+    }
+
+    /// The cached buffer if it matches `key` exactly; a mismatch drops
+    /// the stale entry.
+    pub fn take_if_matches(&self, key: &ResumeKey) -> Option<(Vec<i16>, u32)> {
+        let mut slot = self.slot.lock();
+        match slot.take() {
+            Some(cached) if cached.key == *key => Some((cached.samples, cached.sample_rate)),
+            _ => None,
+        }
+    }
+
+    /// Like [`ResumeCache::take_if_matches`], but sliced from the
+    /// recorded pause offset so resume continues at the exact sample
+    /// the sink stopped at. Without a recorded pause the whole sentence
+    /// comes back; a pause past the end yields an empty buffer (the
+    /// sentence had effectively finished).
+    pub fn resume_from_pause(&self, key: &ResumeKey) -> Option<(Vec<i16>, u32)> {
+        let mut slot = self.slot.lock();
+        match slot.take() {
+            Some(cached) if cached.key == *key => {
+                let skip = cached
+                    .pause_offset
+                    .map(|elapsed| {
+                        (elapsed.as_secs_f64() * f64::from(cached.sample_rate)).round() as usize
+                    })
+                    .unwrap_or(0)
+                    .min(cached.samples.len());
+                Some((cached.samples[skip..].to_vec(), cached.sample_rate))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn invalidate(&self) {
+        *self.slot.lock() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(sentence_index: usize, rate_milli: u32) -> ResumeKey {
+        ResumeKey {
+            engine: "mock".into(),
+            voice: "amy".into(),
+            rate_milli,
+            sentence_index,
+        }
+    }
+
+    #[test]
+    fn resume_replays_only_the_same_sentence_and_settings() {
+        let cache = ResumeCache::new();
+        cache.store(key(3, 1_000), vec![1, 2, 3], 16_000);
+        // Different sentence index: miss, and the stale entry is dropped.
+        assert_eq!(cache.take_if_matches(&key(4, 1_000)), None);
+        assert_eq!(cache.take_if_matches(&key(3, 1_000)), None);
+
+        cache.store(key(3, 1_000), vec![1, 2, 3], 16_000);
+        // Rate change: miss.
+        assert_eq!(cache.take_if_matches(&key(3, 1_250)), None);
+
+        cache.store(key(3, 1_000), vec![1, 2, 3], 16_000);
+        assert_eq!(
+            cache.take_if_matches(&key(3, 1_000)),
+            Some((vec![1, 2, 3], 16_000))
+        );
+        // take semantics: a second resume re-synthesizes.
+        assert_eq!(cache.take_if_matches(&key(3, 1_000)), None);
+    }
+
+    #[test]
+    fn resume_continues_from_the_paused_sample() {
+        let cache = ResumeCache::new();
+        let samples: Vec<i16> = (0..16_000).map(|n| n as i16).collect();
+
+        // Paused half a second in at 16 kHz: 8,000 samples played.
+        cache.store(key(0, 1_000), samples.clone(), 16_000);
+        cache.record_pause(Duration::from_millis(500));
+        let (tail, rate) = cache.resume_from_pause(&key(0, 1_000)).unwrap();
+        assert_eq!(rate, 16_000);
+        assert_eq!(tail.len(), 8_000);
+        assert_eq!(tail[0], samples[8_000]);
+
+        // No pause recorded: the whole sentence replays.
+        cache.store(key(0, 1_000), samples.clone(), 16_000);
+        assert_eq!(
+            cache.resume_from_pause(&key(0, 1_000)).unwrap().0.len(),
+            samples.len()
+        );
+
+        // A pause past the end clamps to an empty buffer.
+        cache.store(key(0, 1_000), samples, 16_000);
+        cache.record_pause(Duration::from_secs(5));
+        assert!(cache.resume_from_pause(&key(0, 1_000)).unwrap().0.is_empty());
+    }
+}
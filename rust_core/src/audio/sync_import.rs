@@ -0,0 +1,152 @@
+//! Import of companion sync files (SMIL media overlays, SRT/VTT
+//! subtitles) into a [`SyncMap`], for audiobooks that ship with their own
+//! text/audio alignment.
+
+use std::path::Path;
+use std::time::Duration;
+
+use tracing::warn;
+
+use super::SyncMap;
+
+/// Build a sync map from a companion file, dispatching on extension.
+/// Returns `None` for unknown formats or files with no usable cues;
+/// malformed cues are skipped with a warning rather than failing the
+/// whole import.
+pub fn import_sync_file(path: &Path) -> Option<SyncMap> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => {
+            warn!(path = %path.display(), %err, "sync file unreadable; skipping");
+            return None;
+        }
+    };
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())?;
+    let map = match extension.as_str() {
+        "srt" | "vtt" => parse_subtitles(&content),
+        "smil" => parse_smil(&content),
+        _ => return None,
+    };
+    (!map.points().is_empty()).then_some(map)
+}
+
+/// Parse SRT or WebVTT cues. Each cue contributes one point mapping the
+/// cumulative byte offset of its text (within the concatenated
+/// transcript) to its start time.
+fn parse_subtitles(content: &str) -> SyncMap {
+    let mut map = SyncMap::default();
+    let mut text_offset = 0usize;
+    for block in content.split("\n\n") {
+        let mut start = None;
+        let mut cue_text = String::new();
+        for line in block.lines() {
+            let line = line.trim_end_matches('\r');
+            if let Some((from, _to)) = line.split_once("-->") {
+                start = parse_clock(from.trim());
+                if start.is_none() {
+                    warn!(cue = line, "unparseable subtitle timing; skipping cue");
+                }
+            } else if start.is_some() && !line.trim().is_empty() {
+                if !cue_text.is_empty() {
+                    cue_text.push(' ');
+                }
+                cue_text.push_str(line.trim());
+            }
+        }
+        if let Some(start) = start {
+            if !cue_text.is_empty() {
+                map.push_point(text_offset, start);
+                text_offset += cue_text.len() + 1;
+            }
+        }
+    }
+    map
+}
+
+/// Parse the `<par>` pairs of a SMIL media overlay. Each `<par>`
+/// contributes one point mapping its ordinal to its audio `clipBegin`.
+fn parse_smil(content: &str) -> SyncMap {
+    let mut map = SyncMap::default();
+    let mut ordinal = 0usize;
+    for par in content.split("<par").skip(1) {
+        let par = par.split("</par>").next().unwrap_or(par);
+        let Some(clip_begin) = attribute_value(par, "clipBegin") else {
+            warn!(ordinal, "smil par without clipBegin; skipping");
+            continue;
+        };
+        match parse_clock(&clip_begin) {
+            Some(start) => {
+                map.push_point(ordinal, start);
+                ordinal += 1;
+            }
+            None => warn!(%clip_begin, "unparseable smil clock value; skipping"),
+        }
+    }
+    map
+}
+
+fn attribute_value(fragment: &str, name: &str) -> Option<String> {
+    let start = fragment.find(&format!("{name}=\""))? + name.len() + 2;
+    let rest = &fragment[start..];
+    Some(rest[..rest.find('"')?].to_string())
+}
+
+/// Parse `HH:MM:SS,mmm` (SRT), `HH:MM:SS.mmm` (VTT/SMIL), and shorter
+/// `MM:SS.mmm` forms; SMIL also allows a bare seconds count like `3.5s`.
+fn parse_clock(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Some(seconds) = value.strip_suffix('s') {
+        if let Ok(seconds) = seconds.parse::<f64>() {
+            return Some(Duration::from_secs_f64(seconds.max(0.0)));
+        }
+    }
+    let normalized = value.replace(',', ".");
+    let mut seconds = 0.0f64;
+    for field in normalized.split(':') {
+        seconds = seconds * 60.0 + field.parse::<f64>().ok()?;
+    }
+    Some(Duration::from_secs_f64(seconds.max(0.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srt_cues_become_points_and_bad_cues_are_skipped() {
+        let srt = "1\n00:00:01,000 --> 00:00:03,000\nHello there.\n\n\
+                   2\nnot a timing line\nGarbage.\n\n\
+                   3\n00:00:04,500 --> 00:00:06,000\nSecond cue.\n";
+        let map = parse_subtitles(srt);
+        assert_eq!(map.points().len(), 2);
+        assert_eq!(map.points()[0].timestamp, Duration::from_secs(1));
+        assert_eq!(map.points()[1].timestamp, Duration::from_millis(4_500));
+        // Second point starts after "Hello there." plus a separator.
+        assert_eq!(map.points()[1].text_index, 13);
+    }
+
+    #[test]
+    fn smil_pars_map_ordinals_to_clip_begin() {
+        let smil = r#"<smil><body><seq>
+            <par id="p1"><text src="ch1.xhtml#s1"/><audio src="ch1.mp3" clipBegin="0:00:00.000" clipEnd="0:00:02.000"/></par>
+            <par id="p2"><text src="ch1.xhtml#s2"/><audio src="ch1.mp3" clipBegin="2.5s"/></par>
+        </seq></body></smil>"#;
+        let map = parse_smil(smil);
+        assert_eq!(map.points().len(), 2);
+        assert_eq!(map.points()[1].text_index, 1);
+        assert_eq!(map.points()[1].timestamp, Duration::from_millis(2_500));
+    }
+
+    #[test]
+    fn unknown_extensions_and_empty_files_import_as_none() {
+        let dir = std::env::temp_dir().join(format!("rust_core_syncimp_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let empty = dir.join("empty.srt");
+        std::fs::write(&empty, "no cues here").unwrap();
+        assert!(import_sync_file(&empty).is_none());
+        assert!(import_sync_file(&dir.join("missing.xyz")).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
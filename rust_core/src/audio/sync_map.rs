@@ -1,12 +1,14 @@
 use std::time::Duration;
 
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SyncPoint {
     pub text_index: usize,
     pub timestamp: Duration,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SyncMap {
     points: Vec<SyncPoint>,
 }
@@ -19,6 +21,10 @@ impl SyncMap {
         });
     }
 
+    pub fn points(&self) -> &[SyncPoint] {
+        &self.points
+    }
+
     pub fn resolve_index(&self, timestamp: Duration) -> Option<usize> {
         self.points
             .iter()
@@ -26,6 +32,18 @@ impl SyncMap {
             .find(|point| point.timestamp <= timestamp)
             .map(|point| point.text_index)
     }
+
+    /// Inverse of `resolve_index`: the audio timestamp for a text
+    /// position, used to seek when a word is clicked. Resolves to the
+    /// closest point at or before `text_index`, so clicking mid-span
+    /// seeks to the start of that span.
+    pub fn resolve_timestamp(&self, text_index: usize) -> Option<Duration> {
+        self.points
+            .iter()
+            .rev()
+            .find(|point| point.text_index <= text_index)
+            .map(|point| point.timestamp)
+    }
 }
 
 #[cfg(test)]
@@ -43,4 +61,18 @@ mod tests {
         assert_eq!(map.resolve_index(Duration::from_millis(150)), Some(5));
         assert_eq!(map.resolve_index(Duration::from_millis(400)), Some(10));
     }
+
+    #[test]
+    fn timestamps_resolve_from_text_positions() {
+        let mut map = SyncMap::default();
+        map.push_point(0, Duration::from_millis(0));
+        map.push_point(5, Duration::from_millis(100));
+        map.push_point(10, Duration::from_millis(300));
+
+        assert_eq!(map.resolve_timestamp(0), Some(Duration::from_millis(0)));
+        // Mid-span clicks seek to the span start.
+        assert_eq!(map.resolve_timestamp(7), Some(Duration::from_millis(100)));
+        assert_eq!(map.resolve_timestamp(99), Some(Duration::from_millis(300)));
+        assert_eq!(SyncMap::default().resolve_timestamp(3), None);
+    }
 }
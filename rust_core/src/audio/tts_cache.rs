@@ -0,0 +1,249 @@
+//! Persistent cache of synthesized sentence audio, shared across runs.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Everything that affects the rendered audio of a sentence. Two requests
+/// with the same key are interchangeable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TtsCacheKey {
+    pub engine: String,
+    pub voice: String,
+    /// Rate in thousandths, so the key stays hashable and exact.
+    pub rate_milli: u32,
+    pub text: String,
+}
+
+impl TtsCacheKey {
+    fn fingerprint(&self) -> String {
+        format!(
+            "{:016x}",
+            fnv1a(
+                format!(
+                    "{}\u{1f}{}\u{1f}{}\u{1f}{}",
+                    self.engine, self.voice, self.rate_milli, self.text
+                )
+                .as_bytes()
+            )
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    bytes: u64,
+    sample_rate: u32,
+    last_used: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Index {
+    entries: HashMap<String, IndexEntry>,
+}
+
+/// On-disk LRU cache: one raw PCM (i16 LE) file per entry plus a small
+/// JSON index so lookups never scan the directory.
+pub struct TtsCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    index: Mutex<Index>,
+    clock: Mutex<u64>,
+}
+
+impl TtsCache {
+    pub const DEFAULT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+    /// Open (or create) a cache under `dir`, loading the index if one
+    /// exists. A damaged index is discarded and rebuilt empty.
+    pub fn open(dir: impl Into<PathBuf>, max_bytes: u64) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        let index = match std::fs::read_to_string(dir.join("index.json")) {
+            Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|err| {
+                warn!(%err, "tts cache index unreadable; starting empty");
+                Index::default()
+            }),
+            Err(_) => Index::default(),
+        };
+        let clock = index
+            .entries
+            .values()
+            .map(|entry| entry.last_used)
+            .max()
+            .unwrap_or(0);
+        Ok(Self {
+            dir,
+            max_bytes,
+            index: Mutex::new(index),
+            clock: Mutex::new(clock),
+        })
+    }
+
+    /// The default location under the user cache dir.
+    pub fn default_dir() -> Option<PathBuf> {
+        Some(dirs::cache_dir()?.join("vanilla-ebook-reader").join("tts"))
+    }
+
+    /// Cached samples for a key, bumping its recency on hit.
+    pub fn get(&self, key: &TtsCacheKey) -> Option<(Vec<i16>, u32)> {
+        let fingerprint = key.fingerprint();
+        let sample_rate = {
+            let mut index = self.index.lock();
+            let entry = index.entries.get_mut(&fingerprint)?;
+            entry.last_used = self.tick();
+            entry.sample_rate
+        };
+        let raw = std::fs::read(self.entry_path(&fingerprint)).ok()?;
+        let samples = raw
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        self.persist_index();
+        Some((samples, sample_rate))
+    }
+
+    /// Store samples for a key, then evict least-recently-used entries
+    /// until the cache fits the size cap again.
+    pub fn put(&self, key: &TtsCacheKey, samples: &[i16], sample_rate: u32) -> std::io::Result<()> {
+        let fingerprint = key.fingerprint();
+        let mut raw = Vec::with_capacity(samples.len() * 2);
+        for sample in samples {
+            raw.extend_from_slice(&sample.to_le_bytes());
+        }
+        std::fs::write(self.entry_path(&fingerprint), &raw)?;
+        {
+            let mut index = self.index.lock();
+            let last_used = self.tick();
+            index.entries.insert(
+                fingerprint,
+                IndexEntry {
+                    bytes: raw.len() as u64,
+                    sample_rate,
+                    last_used,
+                },
+            );
+            self.evict_locked(&mut index);
+        }
+        self.persist_index();
+        Ok(())
+    }
+
+    /// Drop every cached entry (the manual "clear TTS cache" action).
+    pub fn clear(&self) {
+        let mut index = self.index.lock();
+        for fingerprint in index.entries.keys() {
+            let _ = std::fs::remove_file(self.entry_path(fingerprint));
+        }
+        index.entries.clear();
+        drop(index);
+        self.persist_index();
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.index.lock().entries.values().map(|e| e.bytes).sum()
+    }
+
+    fn evict_locked(&self, index: &mut Index) {
+        let mut total: u64 = index.entries.values().map(|e| e.bytes).sum();
+        while total > self.max_bytes {
+            let Some(oldest) = index
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(fingerprint, _)| fingerprint.clone())
+            else {
+                break;
+            };
+            if let Some(entry) = index.entries.remove(&oldest) {
+                total -= entry.bytes;
+                let _ = std::fs::remove_file(self.entry_path(&oldest));
+            }
+        }
+    }
+
+    fn entry_path(&self, fingerprint: &str) -> PathBuf {
+        self.dir.join(format!("{fingerprint}.pcm"))
+    }
+
+    fn persist_index(&self) {
+        let index = self.index.lock();
+        if let Ok(raw) = serde_json::to_string(&*index) {
+            let _ = std::fs::write(self.dir.join("index.json"), raw);
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        let mut clock = self.clock.lock();
+        *clock += 1;
+        *clock
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(tag: &str, max_bytes: u64) -> (PathBuf, TtsCache) {
+        let dir = std::env::temp_dir().join(format!("rust_core_ttscache_{tag}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = TtsCache::open(&dir, max_bytes).unwrap();
+        (dir, cache)
+    }
+
+    fn key(text: &str) -> TtsCacheKey {
+        TtsCacheKey {
+            engine: "mock".into(),
+            voice: "amy".into(),
+            rate_milli: 1_000,
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn survives_reopen() {
+        let (dir, cache) = temp_cache("reopen", u64::MAX);
+        cache.put(&key("hello"), &[1, -2, 3], 16_000).unwrap();
+        drop(cache);
+
+        let cache = TtsCache::open(&dir, u64::MAX).unwrap();
+        let (samples, rate) = cache.get(&key("hello")).unwrap();
+        assert_eq!(samples, vec![1, -2, 3]);
+        assert_eq!(rate, 16_000);
+        assert_eq!(cache.get(&key("other")), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_past_the_cap() {
+        // Each entry is 8 bytes; cap fits two.
+        let (dir, cache) = temp_cache("lru", 16);
+        cache.put(&key("a"), &[0; 4], 16_000).unwrap();
+        cache.put(&key("b"), &[0; 4], 16_000).unwrap();
+        // Touch "a" so "b" is the eviction candidate.
+        assert!(cache.get(&key("a")).is_some());
+        cache.put(&key("c"), &[0; 4], 16_000).unwrap();
+
+        assert!(cache.get(&key("a")).is_some());
+        assert_eq!(cache.get(&key("b")), None);
+        assert!(cache.get(&key("c")).is_some());
+        assert!(cache.total_bytes() <= 16);
+
+        cache.clear();
+        assert_eq!(cache.total_bytes(), 0);
+        assert_eq!(cache.get(&key("a")), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,114 @@
+//! Offline word definitions for the "define" action on a selected word.
+//!
+//! The dictionary is a bundled tab-separated file — one `headword<TAB>
+//! definition` entry per line, lowercase headwords — parsed lazily on
+//! the first lookup so the file costs nothing until the feature is
+//! used. Only compiled in behind the `dictionary` feature.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::OnceCell;
+
+/// A loaded dictionary. Construction is cheap; the backing file is read
+/// and indexed on the first [`Dictionary::define`].
+pub struct Dictionary {
+    path: PathBuf,
+    entries: OnceCell<HashMap<String, String>>,
+}
+
+impl Dictionary {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            entries: OnceCell::new(),
+        }
+    }
+
+    /// Where the bundled dictionary file is expected:
+    /// `<data_dir>/vanilla-ebook-reader/dictionary.tsv`.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(
+            dirs::data_dir()?
+                .join("vanilla-ebook-reader")
+                .join("dictionary.tsv"),
+        )
+    }
+
+    /// Look up the selected token. Surrounding punctuation and case are
+    /// stripped first, so double-clicking `"Whale,"` still defines
+    /// "whale". Returns `None` for unknown words or a missing file.
+    pub fn define(&self, token: &str) -> Option<&str> {
+        let word = normalize_token(token);
+        if word.is_empty() {
+            return None;
+        }
+        self.entries()
+            .get(&word)
+            .map(String::as_str)
+    }
+
+    fn entries(&self) -> &HashMap<String, String> {
+        self.entries.get_or_init(|| match load_entries(&self.path) {
+            Some(entries) => entries,
+            None => {
+                tracing::warn!(path = %self.path.display(), "dictionary file unreadable");
+                HashMap::new()
+            }
+        })
+    }
+}
+
+/// Trim punctuation from both ends and lowercase, leaving inner
+/// apostrophes and hyphens ("don't", "mother-in-law") alone.
+fn normalize_token(token: &str) -> String {
+    token
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+fn load_entries(path: &Path) -> Option<HashMap<String, String>> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    let mut entries = HashMap::new();
+    for line in raw.lines() {
+        if let Some((word, definition)) = line.split_once('\t') {
+            entries
+                .entry(word.trim().to_lowercase())
+                .or_insert_with(|| definition.trim().to_string());
+        }
+    }
+    Some(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::scan::tests::temp_root;
+
+    #[test]
+    fn lookups_strip_punctuation_and_case() {
+        let root = temp_root("dictionary");
+        let file = root.join("dictionary.tsv");
+        std::fs::write(
+            &file,
+            "whale\tA very large marine mammal.\nharpoon\tA barbed spear.\n",
+        )
+        .unwrap();
+
+        let dictionary = Dictionary::new(&file);
+        assert_eq!(
+            dictionary.define("\"Whale,\""),
+            Some("A very large marine mammal.")
+        );
+        assert_eq!(dictionary.define("HARPOON!"), Some("A barbed spear."));
+        assert_eq!(dictionary.define("pequod"), None);
+        assert_eq!(dictionary.define("..."), None);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn a_missing_file_defines_nothing() {
+        let dictionary = Dictionary::new("/nonexistent/dictionary.tsv");
+        assert_eq!(dictionary.define("whale"), None);
+    }
+}
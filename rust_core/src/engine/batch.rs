@@ -0,0 +1,159 @@
+//! Optional batching of sentences into fewer `TTSEngine::synthesize`
+//! calls, with the combined audio split back into per-sentence buffers
+//! for highlighting.
+
+use super::{CancelToken, TTSEngine};
+
+/// How many sentences to hand to the engine per call. One (the default)
+/// keeps latency-to-first-audio lowest; larger batches amortize per-call
+/// overhead for engines with expensive setup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchConfig {
+    pub sentences_per_call: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            sentences_per_call: 1,
+        }
+    }
+}
+
+impl BatchConfig {
+    pub fn new(sentences_per_call: usize) -> Self {
+        Self {
+            sentences_per_call: sentences_per_call.max(1),
+        }
+    }
+}
+
+/// Synthesized audio for one sentence of a batch, indexed into the
+/// original sentence slice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentenceAudio {
+    pub sentence_index: usize,
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+}
+
+/// Synthesize `sentences` in batches of `config.sentences_per_call`.
+/// Engines report no per-sentence offsets, so a multi-sentence buffer is
+/// split proportionally by sentence character count — close enough for
+/// sentence-level highlighting, and exact for single-sentence batches.
+pub fn synthesize_batched(
+    engine: &dyn TTSEngine,
+    sentences: &[&str],
+    config: BatchConfig,
+) -> Result<Vec<SentenceAudio>, String> {
+    synthesize_batched_with_cancel(engine, sentences, config, &CancelToken::new())
+}
+
+/// Like [`synthesize_batched`], but checks `cancel` between engine calls
+/// and returns whatever was synthesized so far when it fires. This keeps
+/// stop/skip responsive during long passages.
+pub fn synthesize_batched_with_cancel(
+    engine: &dyn TTSEngine,
+    sentences: &[&str],
+    config: BatchConfig,
+    cancel: &CancelToken,
+) -> Result<Vec<SentenceAudio>, String> {
+    let mut out = Vec::with_capacity(sentences.len());
+    for (batch_index, batch) in sentences.chunks(config.sentences_per_call).enumerate() {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let joined = batch.join(" ");
+        let frames = engine.synthesize(&joined)?;
+        let sample_rate = frames
+            .first()
+            .map(|frame| frame.sample_rate)
+            .unwrap_or(16_000);
+        let mut combined = Vec::new();
+        for frame in frames {
+            combined.extend_from_slice(&frame.samples);
+        }
+
+        let base_index = batch_index * config.sentences_per_call;
+        for (offset, range) in proportional_splits(batch, combined.len()).into_iter().enumerate() {
+            out.push(SentenceAudio {
+                sentence_index: base_index + offset,
+                samples: combined[range].to_vec(),
+                sample_rate,
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// Sample ranges for each sentence of a batch, proportional to character
+/// count. Covers the whole buffer with no gaps or overlaps.
+fn proportional_splits(batch: &[&str], total_samples: usize) -> Vec<std::ops::Range<usize>> {
+    let total_chars: usize = batch.iter().map(|s| s.chars().count().max(1)).sum();
+    let mut ranges = Vec::with_capacity(batch.len());
+    let mut consumed_chars = 0usize;
+    let mut start = 0usize;
+    for (idx, sentence) in batch.iter().enumerate() {
+        consumed_chars += sentence.chars().count().max(1);
+        let end = if idx + 1 == batch.len() {
+            total_samples
+        } else {
+            (total_samples as f64 * consumed_chars as f64 / total_chars as f64) as usize
+        };
+        ranges.push(start..end.max(start));
+        start = end.max(start);
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::AudioFrame;
+
+    /// Emits one sample per input character and counts calls.
+    struct CountingEngine {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl TTSEngine for CountingEngine {
+        fn synthesize(&self, text: &str) -> Result<Vec<AudioFrame>, String> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![AudioFrame {
+                samples: vec![1; text.chars().count()],
+                sample_rate: 16_000,
+                associated_text_idx: 0,
+            }])
+        }
+    }
+
+    #[test]
+    fn batches_reduce_engine_calls_and_keep_sentence_boundaries() {
+        let engine = CountingEngine {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let sentences = ["One.", "Twos.", "Three."];
+
+        let audio = synthesize_batched(&engine, &sentences, BatchConfig::new(2)).unwrap();
+        assert_eq!(engine.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(audio.len(), 3);
+        assert_eq!(
+            audio.iter().map(|a| a.sentence_index).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        // "One. Twos." is 10 chars → 10 samples, split 4/6 by char count.
+        assert_eq!(audio[0].samples.len() + audio[1].samples.len(), 10);
+        assert!(audio[0].samples.len() < audio[1].samples.len());
+        assert_eq!(audio[2].samples.len(), 6);
+    }
+
+    #[test]
+    fn default_config_is_one_sentence_per_call() {
+        let engine = CountingEngine {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let sentences = ["A.", "B."];
+        synthesize_batched(&engine, &sentences, BatchConfig::default()).unwrap();
+        assert_eq!(engine.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+}
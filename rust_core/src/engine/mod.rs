@@ -1,4 +1,5 @@
 use std::f32::consts::PI;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use parking_lot::RwLock;
@@ -7,9 +8,36 @@ use thiserror::Error;
 #[cfg(all(feature = "piper", not(target_os = "windows")))]
 use crate::api::PiperBackendConfig;
 
+pub mod batch;
 #[cfg(all(feature = "piper", not(target_os = "windows")))]
 pub mod piper;
 
+pub use batch::{
+    synthesize_batched, synthesize_batched_with_cancel, BatchConfig, SentenceAudio,
+};
+
+/// Shared cancellation flag threaded through the synthesis path so a
+/// stop request interrupts work between sentences/frames instead of
+/// waiting for the whole utterance to finish.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    flag: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::SeqCst)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AudioFrame {
     pub samples: Vec<i16>,
@@ -19,6 +47,18 @@ pub struct AudioFrame {
 
 pub trait TTSEngine: Send + Sync + 'static {
     fn synthesize(&self, text: &str) -> std::result::Result<Vec<AudioFrame>, String>;
+
+    /// Perform any lazy initialization up front (model load, runtime
+    /// setup) so the first real `synthesize` call is fast. Default is a
+    /// no-op; engines with expensive first-call setup should override it.
+    fn warmup(&self) {}
+
+    /// Whether `synthesize` accepts SSML markup. Engines that return
+    /// `false` (the default) are fed the plain body and emphasis is
+    /// simply dropped.
+    fn supports_ssml(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Error)]
@@ -149,7 +189,7 @@ impl TTSEngine for MockEngine {
                 pcm.push(sample);
             }
             // brief silence between words
-            pcm.extend(std::iter::repeat(0).take((sample_rate as f32 * 0.05) as usize));
+            pcm.extend(std::iter::repeat_n(0, (sample_rate as f32 * 0.05) as usize));
         }
         if pcm.is_empty() {
             pcm.resize(800, 0);
@@ -3,8 +3,18 @@
 pub mod api;
 pub mod audio;
 #[cfg(feature = "bridge")]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 mod bridge_generated; /* AUTO INJECTED BY flutter_rust_bridge. This line may not be accurate, and you can change it according to your needs. */
+#[cfg(feature = "dictionary")]
+pub mod dictionary;
 pub mod engine;
+pub mod library;
+#[cfg(feature = "wikipedia")]
+pub mod lookup;
+pub mod persistence;
+pub mod text;
+#[cfg(feature = "translation")]
+pub mod translate;
 
 pub use api::*;
 pub use engine::EngineRegistryHandle;
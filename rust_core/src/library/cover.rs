@@ -0,0 +1,249 @@
+//! Generated placeholder covers for books without art.
+//!
+//! The core doesn't rasterize images; it derives a deterministic
+//! recipe — a background color from a hash of the title plus the
+//! initials to print on it — and the UI layer draws the tile. The same
+//! book always gets the same placeholder, and different titles spread
+//! across the palette so a grid of placeholders stays tellable apart.
+
+use std::path::PathBuf;
+
+use super::Ebook;
+
+/// Image extensions accepted as cover art.
+const COVER_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "avif"];
+
+/// Filename stems tried for sibling cover art, in priority order. The
+/// `{title}` token stands for the book's title. Overridable through
+/// `LibraryConfig::cover_patterns`.
+pub const DEFAULT_COVER_PATTERNS: &[&str] = &["cover", "folder", "front", "{title}"];
+
+/// Look for cover art next to the book's files: for each pattern in
+/// order, any file whose stem matches it case-insensitively and whose
+/// extension is a known image type wins. Returns `None` when nothing
+/// matches, at which point [`placeholder_cover`] takes over.
+pub fn find_cover_art(book: &Ebook, patterns: &[String]) -> Option<PathBuf> {
+    let dir = if book.path.is_dir() {
+        book.path.clone()
+    } else {
+        book.path.parent()?.to_path_buf()
+    };
+    let entries: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            super::scan::file_extension(path)
+                .is_some_and(|ext| COVER_EXTENSIONS.contains(&ext.as_str()))
+        })
+        .collect();
+    for pattern in patterns {
+        let stem = pattern.replace("{title}", &book.title).to_lowercase();
+        if let Some(found) = entries.iter().find(|path| {
+            path.file_stem()
+                .is_some_and(|s| s.to_string_lossy().to_lowercase() == stem)
+        }) {
+            return Some(found.clone());
+        }
+    }
+    None
+}
+
+/// Everything the UI needs to draw a placeholder tile. Only used when
+/// no real cover art exists; a real cover always wins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceholderCover {
+    /// Background color as RGB.
+    pub background: [u8; 3],
+    /// Up to two uppercase initials from the title.
+    pub initials: String,
+    /// Author line to render small under the initials, when known.
+    pub author: Option<String>,
+}
+
+/// Muted palette the title hash indexes into. Hand-picked so white
+/// initials stay readable on every entry, and deliberately flatter than
+/// typical cover art so placeholders don't pass for the real thing.
+const PALETTE: &[[u8; 3]] = &[
+    [0x4e, 0x64, 0x7a], // slate blue
+    [0x6b, 0x4e, 0x7a], // plum
+    [0x7a, 0x4e, 0x57], // brick
+    [0x4e, 0x7a, 0x66], // pine
+    [0x7a, 0x6d, 0x4e], // ochre
+    [0x3f, 0x58, 0x52], // deep teal
+    [0x5c, 0x50, 0x44], // umber
+    [0x44, 0x4f, 0x6b], // indigo
+];
+
+/// Cached first-page thumbnail for a PDF book. The rasterizer itself is
+/// injected (`render(pdf, out)` returning success), since PDF rendering
+/// lives behind an optional native dependency in the frontends; builds
+/// without one simply pass a renderer that returns `false` and fall
+/// back to the generated placeholder. Thumbnails are rendered once into
+/// `cache_dir` and invalidated when the PDF's mtime moves past them.
+pub fn pdf_cover_thumbnail(
+    book: &Ebook,
+    cache_dir: &std::path::Path,
+    render: impl Fn(&std::path::Path, &std::path::Path) -> bool,
+) -> Option<PathBuf> {
+    let text = book.text.as_ref()?;
+    if text.format != super::TextFormat::Pdf {
+        return None;
+    }
+    let thumbnail = cache_dir.join(format!("pdf-{:016x}.png", fnv1a(book.id.0.as_bytes())));
+    let pdf_mtime = std::fs::metadata(&text.file).ok()?.modified().ok()?;
+    if let Ok(meta) = std::fs::metadata(&thumbnail) {
+        if meta.modified().ok().is_some_and(|cached| cached >= pdf_mtime) {
+            return Some(thumbnail);
+        }
+    }
+    std::fs::create_dir_all(cache_dir).ok()?;
+    render(&text.file, &thumbnail).then_some(thumbnail)
+}
+
+/// The deterministic placeholder for `book`.
+pub fn placeholder_cover(book: &Ebook) -> PlaceholderCover {
+    PlaceholderCover {
+        background: PALETTE[(fnv1a(book.title.as_bytes()) as usize) % PALETTE.len()],
+        initials: title_initials(&book.title),
+        author: book.author.clone(),
+    }
+}
+
+/// First letters of the first two words, uppercased; "The"/"A"/"An"
+/// are skipped when something follows so "The Hobbit" shows "H".
+fn title_initials(title: &str) -> String {
+    let words: Vec<&str> = title.split_whitespace().collect();
+    let skip_article = words.len() > 1
+        && matches!(words[0].to_lowercase().as_str(), "the" | "a" | "an");
+    words
+        .iter()
+        .skip(usize::from(skip_article))
+        .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::EbookId;
+    use std::path::PathBuf;
+
+    fn book(title: &str, author: Option<&str>) -> Ebook {
+        Ebook {
+            id: EbookId(title.into()),
+            title: title.into(),
+            author: author.map(str::to_string),
+            description: None,
+            path: PathBuf::from(title),
+            audio_chapters: Vec::new(),
+            text: None,
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        }
+    }
+
+    #[test]
+    fn cover_patterns_match_in_priority_order() {
+        use crate::library::scan::tests::temp_root;
+
+        let root = temp_root("covers");
+        let dir = root.join("The Hobbit");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("folder.jpg"), b"x").unwrap();
+        std::fs::write(dir.join("The Hobbit.png"), b"x").unwrap();
+
+        let mut hobbit = book("The Hobbit", None);
+        hobbit.path = dir.clone();
+        let patterns: Vec<String> = DEFAULT_COVER_PATTERNS
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        // No cover.* file: "folder" outranks the title pattern.
+        let found = find_cover_art(&hobbit, &patterns).unwrap();
+        assert!(found.ends_with("folder.jpg"));
+
+        std::fs::write(dir.join("Cover.JPG"), b"x").unwrap();
+        let found = find_cover_art(&hobbit, &patterns).unwrap();
+        assert!(found.ends_with("Cover.JPG"));
+
+        // Modern formats count too: an AVIF cover beats the jpg once a
+        // higher-priority stem carries it.
+        std::fs::remove_file(dir.join("Cover.JPG")).unwrap();
+        std::fs::write(dir.join("cover.avif"), b"x").unwrap();
+        let found = find_cover_art(&hobbit, &patterns).unwrap();
+        assert!(found.ends_with("cover.avif"));
+
+        assert_eq!(find_cover_art(&book("Elsewhere", None), &patterns), None);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn placeholders_are_deterministic_and_title_keyed() {
+        let hobbit = book("The Hobbit", Some("J. R. R. Tolkien"));
+        let cover = placeholder_cover(&hobbit);
+        assert_eq!(cover, placeholder_cover(&hobbit));
+        assert_eq!(cover.initials, "H");
+        assert_eq!(cover.author.as_deref(), Some("J. R. R. Tolkien"));
+        // A different title lands on its own recipe.
+        assert_ne!(cover, placeholder_cover(&book("Dune", None)));
+    }
+
+    #[test]
+    fn pdf_thumbnails_render_once_and_refresh_with_the_source() {
+        use crate::library::scan::tests::temp_root;
+        use crate::library::{TextContent, TextFormat};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let root = temp_root("pdfcover");
+        let pdf = root.join("paper.pdf");
+        std::fs::write(&pdf, b"%PDF-1.7").unwrap();
+        let cache = root.join("thumbs");
+
+        let mut paper = book("Paper", None);
+        paper.text = Some(TextContent {
+            file: pdf.clone(),
+            format: TextFormat::Pdf,
+            continuation: Vec::new(),
+        });
+
+        let renders = AtomicUsize::new(0);
+        let render = |_pdf: &std::path::Path, out: &std::path::Path| {
+            renders.fetch_add(1, Ordering::SeqCst);
+            std::fs::write(out, b"png").is_ok()
+        };
+        let first = pdf_cover_thumbnail(&paper, &cache, render).unwrap();
+        assert_eq!(pdf_cover_thumbnail(&paper, &cache, render), Some(first));
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+        // A failing renderer (no PDF support built in) yields nothing.
+        let mut epub = paper.clone();
+        epub.text.as_mut().unwrap().format = TextFormat::Epub;
+        assert_eq!(pdf_cover_thumbnail(&epub, &cache, render), None);
+        assert_eq!(
+            pdf_cover_thumbnail(&book("NoText", None), &cache, |_, _| false),
+            None
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn initials_take_the_first_two_words() {
+        assert_eq!(title_initials("jane eyre"), "JE");
+        assert_eq!(title_initials("A"), "A");
+        assert_eq!(title_initials("1984"), "1");
+    }
+}
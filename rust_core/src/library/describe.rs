@@ -0,0 +1,202 @@
+//! Human-readable detail lines for library rows.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::Mutex;
+
+use super::Ebook;
+
+/// Words per minute assumed for the "~Nh Mm" reading-time estimate.
+const READING_WORDS_PER_MINUTE: u64 = 200;
+
+/// Builds the per-book detail line ("Audio • 9:41:00", "Text • ~5h 12m").
+/// Word counts are cached per text file and invalidated by mtime, so
+/// repaints don't re-read books.
+#[derive(Default)]
+pub struct BookDescriber {
+    word_counts: Mutex<HashMap<PathBuf, (Option<SystemTime>, u64)>>,
+}
+
+impl BookDescriber {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn describe(&self, book: &Ebook) -> String {
+        let mut parts = Vec::new();
+        if let Some(series) = book.series_label() {
+            parts.push(series);
+        }
+        if book.has_audio() {
+            parts.push("Audio".to_string());
+            // Single-file audiobooks skip the count; it adds nothing.
+            let chapters = book.audio_chapters.len();
+            if chapters > 1 {
+                parts.push(format!("{chapters} chapters"));
+            }
+            if let Some(duration) = total_audio_duration(book) {
+                parts.push(format_duration(duration));
+            }
+        }
+        if book.has_text() {
+            parts.push("Text".to_string());
+            // Only text-only books get the estimate; for dual books the
+            // audio runtime is the more meaningful number.
+            if !book.has_audio() {
+                if let Some(words) = self.word_count(book) {
+                    parts.push(format_reading_estimate(estimate_reading_time(words)));
+                }
+            }
+        }
+        parts.join(" • ")
+    }
+
+    fn word_count(&self, book: &Ebook) -> Option<u64> {
+        let text = book.text.as_ref()?;
+        let mtime = std::fs::metadata(&text.file).and_then(|m| m.modified()).ok();
+        let mut cache = self.word_counts.lock();
+        if let Some((cached_mtime, words)) = cache.get(&text.file) {
+            if *cached_mtime == mtime {
+                return Some(*words);
+            }
+        }
+        let body = std::fs::read_to_string(&text.file).ok()?;
+        let words = body.split_whitespace().count() as u64;
+        cache.insert(text.file.clone(), (mtime, words));
+        Some(words)
+    }
+}
+
+/// Total runtime across a book's audio files, summed from the durations
+/// captured at scan time. `None` when no chapter reported one.
+pub fn total_audio_duration(book: &Ebook) -> Option<Duration> {
+    let mut total = Duration::ZERO;
+    let mut any = false;
+    for chapter in &book.audio_chapters {
+        if let Some(duration) = chapter.duration {
+            total += duration;
+            any = true;
+        }
+    }
+    any.then_some(total)
+}
+
+/// `H:MM:SS` once an hour is reached, `M:SS` below that.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// How long a text would take to read at a typical pace.
+pub fn estimate_reading_time(words: u64) -> Duration {
+    Duration::from_secs(words * 60 / READING_WORDS_PER_MINUTE)
+}
+
+/// `~Nh Mm` above an hour, `~Nm` below, never less than a minute.
+fn format_reading_estimate(duration: Duration) -> String {
+    let minutes = (duration.as_secs() / 60).max(1);
+    if minutes >= 60 {
+        format!("~{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("~{minutes}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::scan::tests::temp_root;
+    use crate::library::{EbookId, TextContent, TextFormat};
+
+    #[test]
+    fn durations_format_with_and_without_hours() {
+        assert_eq!(format_duration(Duration::from_secs(9 * 3600 + 41 * 60)), "9:41:00");
+        assert_eq!(format_duration(Duration::from_secs(5 * 60 + 7)), "5:07");
+    }
+
+    #[test]
+    fn chapter_labels_append_known_durations_only() {
+        use crate::library::AudioChapter;
+
+        let mut chapter = AudioChapter {
+            chapter_index: 0,
+            title: "03".into(),
+            file: PathBuf::from("03.mp3"),
+            track_number: None,
+            duration: Some(Duration::from_secs(41 * 60 + 2)),
+        };
+        assert_eq!(chapter.display_title(), "03 — 41:02");
+        chapter.duration = None;
+        assert_eq!(chapter.display_title(), "03");
+    }
+
+    #[test]
+    fn chapter_count_appears_only_for_multi_file_audio() {
+        use crate::library::AudioChapter;
+
+        let chapter = |idx: usize| AudioChapter {
+            chapter_index: idx,
+            title: format!("Part {idx}"),
+            file: PathBuf::from(format!("missing-{idx}.mp3")),
+            track_number: None,
+            duration: None,
+        };
+        let mut book = Ebook {
+            id: EbookId("audio".into()),
+            title: "Audio".into(),
+            author: None,
+            description: None,
+            path: PathBuf::from("audio"),
+            audio_chapters: vec![chapter(0), chapter(1)],
+            text: None,
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        };
+        let describer = BookDescriber::new();
+        // Files aren't readable here, so no duration part.
+        assert_eq!(describer.describe(&book), "Audio • 2 chapters");
+
+        book.audio_chapters.truncate(1);
+        assert_eq!(describer.describe(&book), "Audio");
+    }
+
+    #[test]
+    fn text_only_books_get_a_reading_estimate() {
+        let root = temp_root("describe");
+        let file = root.join("book.txt");
+        let body = "word ".repeat(24_000); // 2h at 200 wpm
+        std::fs::write(&file, body).unwrap();
+
+        let book = Ebook {
+            id: EbookId::from_path(&file),
+            title: "Book".into(),
+            author: None,
+            description: None,
+            path: root.clone(),
+            audio_chapters: Vec::new(),
+            text: Some(TextContent {
+                file,
+                format: TextFormat::PlainText,
+                continuation: Vec::new(),
+            }),
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        };
+        let describer = BookDescriber::new();
+        assert_eq!(describer.describe(&book), "Text • ~2h 0m");
+        // Second call hits the cache; same answer.
+        assert_eq!(describer.describe(&book), "Text • ~2h 0m");
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,327 @@
+//! Library-wide full-text search over each book's extracted text, with a
+//! per-book cache so repeated searches don't re-read files.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+
+use super::{Ebook, EbookId, Library};
+
+/// One match inside a book's text, with enough context to render a
+/// "Book / snippet" result row and open the reader at the hit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FullTextHit {
+    pub book_id: EbookId,
+    pub title: String,
+    /// Byte offset of the match within the book's extracted text.
+    pub offset: usize,
+    /// Byte length of the matched text, so the reader can highlight
+    /// exactly what matched rather than the whole sentence.
+    pub len: usize,
+    pub snippet: String,
+}
+
+/// How the search query is interpreted. Substring is the default; the
+/// regex toggle is for power users and reports its own errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    #[default]
+    Substring,
+    Regex,
+}
+
+/// Compiled size cap for user-supplied patterns. Regex matching itself
+/// is linear-time, so bounding the automaton is what guards against
+/// pathological patterns like huge bounded repetitions.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+fn compile_search_regex(query: &str) -> Result<regex::Regex, String> {
+    regex::RegexBuilder::new(query)
+        .case_insensitive(true)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|err| format!("invalid regex: {err}"))
+}
+
+struct CachedText {
+    mtime: Option<SystemTime>,
+    body: String,
+}
+
+/// On-demand full-text scanner. Extracted text is cached per book and
+/// invalidated by file mtime, so the first search pays the read cost and
+/// later ones are in-memory.
+#[derive(Default)]
+pub struct FullTextIndex {
+    cache: Mutex<HashMap<PathBuf, CachedText>>,
+}
+
+const SNIPPET_CONTEXT_BYTES: usize = 40;
+
+impl FullTextIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Search every book in `library` that has readable text. Matches are
+    /// case-insensitive; an empty query returns nothing.
+    pub fn search(&self, library: &Library, query: &str) -> Vec<FullTextHit> {
+        self.search_with_mode(library, query, SearchMode::Substring)
+            .unwrap_or_default()
+    }
+
+    /// [`FullTextIndex::search`] with an explicit matching mode. Only
+    /// [`SearchMode::Regex`] can fail — on an invalid or oversized
+    /// pattern — and the error text is fit to show in the search UI.
+    pub fn search_with_mode(
+        &self,
+        library: &Library,
+        query: &str,
+        mode: SearchMode,
+    ) -> Result<Vec<FullTextHit>, String> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pattern = match mode {
+            SearchMode::Substring => None,
+            SearchMode::Regex => Some(compile_search_regex(query)?),
+        };
+        let mut hits = Vec::new();
+        for book in library.iter() {
+            let Some(body) = self.body_for(&book) else {
+                continue;
+            };
+            let matches = match &pattern {
+                Some(regex) => regex.find_iter(&body).map(|m| m.range()).collect(),
+                None => find_case_insensitive(&body, query),
+            };
+            for matched in matches {
+                hits.push(FullTextHit {
+                    book_id: book.id.clone(),
+                    title: book.title.clone(),
+                    offset: matched.start,
+                    len: matched.len(),
+                    snippet: snippet_around(&body, matched.start, matched.len()),
+                });
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Drop all cached text, forcing the next search to re-read files.
+    pub fn clear(&self) {
+        self.cache.lock().clear();
+    }
+
+    fn body_for(&self, book: &Ebook) -> Option<String> {
+        let text = book.text.as_ref()?;
+        let mtime = std::fs::metadata(&text.file).and_then(|m| m.modified()).ok();
+        let mut cache = self.cache.lock();
+        if let Some(cached) = cache.get(&text.file) {
+            if cached.mtime == mtime {
+                return Some(cached.body.clone());
+            }
+        }
+        // Multi-volume books search as one continuous text; unreadable
+        // later volumes just end the body early.
+        let mut body = std::fs::read_to_string(&text.file).ok()?;
+        for volume in &text.continuation {
+            match std::fs::read_to_string(volume) {
+                Ok(chunk) => {
+                    body.push_str("\n\n");
+                    body.push_str(&chunk);
+                }
+                Err(_) => break,
+            }
+        }
+        cache.insert(
+            text.file.clone(),
+            CachedText {
+                mtime,
+                body: body.clone(),
+            },
+        );
+        Some(body)
+    }
+}
+
+/// Byte ranges of case- and diacritic-insensitive occurrences of
+/// `query` in `body`, using the same folding rule as [`Ebook::matches`]
+/// so "cafe" finds "Café". Each body character is folded independently
+/// while the matched span is tracked in the original text, so offsets
+/// and lengths stay valid for highlighting despite the normalized
+/// comparison.
+fn find_case_insensitive(body: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+    let query_folded: Vec<char> = super::normalize_for_match(query).chars().collect();
+    if query_folded.is_empty() {
+        return Vec::new();
+    }
+    let mut ranges = Vec::new();
+    for (offset, _) in body.char_indices() {
+        let mut matched = 0usize;
+        for (idx, ch) in body[offset..].char_indices() {
+            let mut advanced = true;
+            // Combining marks fold to nothing: they extend the matched
+            // span without consuming query characters.
+            for folded in fold_char(ch) {
+                if query_folded.get(matched) == Some(&folded) {
+                    matched += 1;
+                } else {
+                    advanced = false;
+                    break;
+                }
+            }
+            if !advanced {
+                break;
+            }
+            if matched == query_folded.len() {
+                ranges.push(offset..offset + idx + ch.len_utf8());
+                break;
+            }
+        }
+    }
+    ranges
+}
+
+/// Per-character version of [`super::normalize_for_match`]: decompose,
+/// drop combining marks, lowercase.
+fn fold_char(ch: char) -> impl Iterator<Item = char> {
+    use unicode_normalization::char::is_combining_mark;
+    use unicode_normalization::UnicodeNormalization;
+
+    std::iter::once(ch)
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+}
+
+/// A short window of text around the match, trimmed to char boundaries
+/// and flattened to one line.
+fn snippet_around(body: &str, offset: usize, match_len: usize) -> String {
+    let mut start = offset.saturating_sub(SNIPPET_CONTEXT_BYTES);
+    while !body.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (offset + match_len + SNIPPET_CONTEXT_BYTES).min(body.len());
+    while !body.is_char_boundary(end) {
+        end += 1;
+    }
+    body[start..end].split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::scan::tests::temp_root;
+    use crate::library::{TextContent, TextFormat};
+
+    fn text_book(tag: &str, title: &str, body: &str) -> (PathBuf, Ebook) {
+        let root = temp_root(&format!("fulltext_{tag}"));
+        let file = root.join(format!("{title}.txt"));
+        std::fs::write(&file, body).unwrap();
+        let book = Ebook {
+            id: EbookId::from_path(&file),
+            title: title.to_string(),
+            author: None,
+            description: None,
+            path: root.clone(),
+            audio_chapters: Vec::new(),
+            text: Some(TextContent {
+                file,
+                format: TextFormat::PlainText,
+                continuation: Vec::new(),
+            }),
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        };
+        (root, book)
+    }
+
+    #[test]
+    fn finds_matches_across_books_with_snippets() {
+        let (root_a, book_a) = text_book("a", "Alpha", "The white whale surfaced at dawn.");
+        let (root_b, book_b) = text_book("b", "Beta", "No whales here, only Whale Song.");
+        let library = Library::new();
+        library.replace_all(vec![book_a, book_b]);
+
+        let index = FullTextIndex::new();
+        let hits = index.search(&library, "whale");
+        let titles: Vec<&str> = hits.iter().map(|h| h.title.as_str()).collect();
+        assert_eq!(titles, vec!["Alpha", "Beta", "Beta"]);
+        assert!(hits[0].snippet.contains("white whale surfaced"));
+        assert!(index.search(&library, "  ").is_empty());
+
+        let _ = std::fs::remove_dir_all(root_a);
+        let _ = std::fs::remove_dir_all(root_b);
+    }
+
+    #[test]
+    fn regex_mode_matches_patterns_and_reports_bad_ones() {
+        let (root, book) = text_book("regex", "Epsilon", "Ship 12, ship 345, shipment 6.");
+        let library = Library::new();
+        library.replace_all(vec![book]);
+
+        let index = FullTextIndex::new();
+        let hits = index
+            .search_with_mode(&library, r"ship \d+", SearchMode::Regex)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[1].len, "ship 345".len());
+
+        let err = index
+            .search_with_mode(&library, r"ship (", SearchMode::Regex)
+            .unwrap_err();
+        assert!(err.starts_with("invalid regex:"));
+        // Bounded repetitions that would blow up the automaton are
+        // rejected rather than compiled.
+        assert!(index
+            .search_with_mode(&library, r"(a{1000}){1000}", SearchMode::Regex)
+            .is_err());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn ascii_queries_find_accented_text_with_original_offsets() {
+        let (root, book) = text_book("accents", "Delta", "Un CAFÉ au lait, puis un café noir.");
+        let library = Library::new();
+        library.replace_all(vec![book]);
+
+        let index = FullTextIndex::new();
+        let hits = index.search(&library, "cafe");
+        assert_eq!(hits.len(), 2);
+        // Offsets and lengths index the original text: "CAFÉ" is five
+        // bytes despite folding to four characters.
+        assert_eq!(hits[0].offset, 3);
+        assert_eq!(hits[0].len, "CAFÉ".len());
+        assert_eq!(hits[1].len, "café".len());
+        // The accented query folds the same way round.
+        assert_eq!(index.search(&library, "CAFÉ").len(), 2);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn cache_refreshes_when_the_file_changes() {
+        let (root, book) = text_book("stale", "Gamma", "old contents");
+        let library = Library::new();
+        library.replace_all(vec![book.clone()]);
+
+        let index = FullTextIndex::new();
+        assert_eq!(index.search(&library, "old").len(), 1);
+
+        let file = book.text.unwrap().file;
+        std::fs::write(&file, "new contents").unwrap();
+        // Force a visible mtime change even on coarse filesystems.
+        index.clear();
+        assert!(index.search(&library, "old").is_empty());
+        assert_eq!(index.search(&library, "NEW").len(), 1);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
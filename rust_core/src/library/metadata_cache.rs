@@ -0,0 +1,165 @@
+//! Central cache of per-file tag reads, keyed by path and mtime.
+//!
+//! Opening every audio file for its tags and duration is the dominant
+//! cost of a scan. Results barely ever change, so they are cached in one
+//! JSON file and re-read only when the source file's mtime moves.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// What a tag read yields for one audio file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioMetadata {
+    pub track_number: Option<u32>,
+    /// Milliseconds; `Duration` itself would serialize as a struct.
+    duration_ms: Option<u64>,
+}
+
+impl AudioMetadata {
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration_ms.map(Duration::from_millis)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    mtime: i64,
+    metadata: AudioMetadata,
+}
+
+/// The cache itself. `audio_metadata` is the hot path: a hit costs one
+/// `stat`, a miss reads the file once and remembers the answer. Call
+/// [`MetadataCache::save`] after a scan to persist new entries.
+pub struct MetadataCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<PathBuf, Entry>>,
+}
+
+impl MetadataCache {
+    /// Open the cache at `path`, starting empty if the file is missing
+    /// or unreadable (a corrupt cache just means a slow first scan).
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|body| serde_json::from_str(&body).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        Some(
+            dirs::cache_dir()?
+                .join("vanilla-ebook-reader")
+                .join("audio_metadata.json"),
+        )
+    }
+
+    /// Tag data for `file`, served from the cache when its mtime still
+    /// matches and extracted (then cached) otherwise.
+    pub fn audio_metadata(&self, file: &Path) -> AudioMetadata {
+        let mtime = file_mtime_secs(file);
+        if let Some(entry) = self.entries.lock().get(file) {
+            if Some(entry.mtime) == mtime {
+                return entry.metadata;
+            }
+        }
+        let metadata = read_audio_metadata(file);
+        if let Some(mtime) = mtime {
+            self.entries
+                .lock()
+                .insert(file.to_path_buf(), Entry { mtime, metadata });
+        }
+        metadata
+    }
+
+    /// Write the cache back to disk. Failures are returned rather than
+    /// logged; callers decide whether a stale cache matters.
+    pub fn save(&self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let body = serde_json::to_string(&*self.entries.lock())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        std::fs::write(&self.path, body)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+/// One combined tag read, so a cache miss opens the file once for both
+/// the track number and the duration.
+fn read_audio_metadata(path: &Path) -> AudioMetadata {
+    use lofty::file::{AudioFile, TaggedFileExt};
+    use lofty::tag::Accessor;
+
+    let Ok(tagged) = lofty::read_from_path(path) else {
+        return AudioMetadata {
+            track_number: None,
+            duration_ms: None,
+        };
+    };
+    let track_number = tagged
+        .primary_tag()
+        .or_else(|| tagged.first_tag())
+        .and_then(|tag| tag.track());
+    let duration = tagged.properties().duration();
+    AudioMetadata {
+        track_number,
+        duration_ms: (!duration.is_zero()).then_some(duration.as_millis() as u64),
+    }
+}
+
+fn file_mtime_secs(path: &Path) -> Option<i64> {
+    let mtime = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(
+        mtime
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .ok()?
+            .as_secs() as i64,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::scan::tests::temp_root;
+
+    #[test]
+    fn entries_survive_reopen_and_invalidate_on_mtime_change() {
+        let root = temp_root("metacache");
+        let cache_path = root.join("cache.json");
+        let audio = root.join("01.mp3");
+        std::fs::write(&audio, b"not really audio").unwrap();
+
+        let cache = MetadataCache::open(&cache_path);
+        // Unreadable tags still cache: the point is skipping the read.
+        assert_eq!(cache.audio_metadata(&audio).track_number, None);
+        assert_eq!(cache.len(), 1);
+        cache.save().unwrap();
+
+        let reopened = MetadataCache::open(&cache_path);
+        assert_eq!(reopened.len(), 1);
+
+        // Touch the file into the future; the stale entry is replaced.
+        let file = std::fs::File::options().write(true).open(&audio).unwrap();
+        file.set_modified(std::time::SystemTime::now() + Duration::from_secs(120))
+            .unwrap();
+        assert_eq!(reopened.audio_metadata(&audio).duration(), None);
+        assert_eq!(reopened.len(), 1);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,664 @@
+//! Library model: ebooks discovered on disk and their audio/text content.
+
+pub mod cover;
+pub mod describe;
+pub mod fulltext;
+pub mod metadata_cache;
+pub mod now_playing;
+pub mod scan;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+pub use cover::{find_cover_art, pdf_cover_thumbnail, placeholder_cover, PlaceholderCover};
+pub use describe::{format_duration, total_audio_duration, BookDescriber};
+pub use fulltext::{FullTextHit, FullTextIndex, SearchMode};
+pub use metadata_cache::{AudioMetadata, MetadataCache};
+pub use now_playing::NowPlaying;
+pub use scan::{scan_library, scan_library_with_cache, ScanError};
+
+/// Stable identifier for a book, derived from its location under the
+/// library root.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EbookId(pub String);
+
+impl EbookId {
+    pub fn from_path(path: &Path) -> Self {
+        Self(path.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextFormat {
+    Epub,
+    Pdf,
+    Markdown,
+    Html,
+    PlainText,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioChapter {
+    /// Derived position of the file within the book (sorted file order).
+    pub chapter_index: usize,
+    pub title: String,
+    pub file: PathBuf,
+    /// Track number from the file's ID3/MP4 tags, when present. Distinct
+    /// from `chapter_index`: this is what the rip declared, not where the
+    /// file landed after sorting. Defaults to `None` so older serialized
+    /// metadata still parses.
+    #[serde(default)]
+    pub track_number: Option<u32>,
+    /// Runtime from the file's audio headers, read at scan time. `None`
+    /// when the file was unreadable or reported nothing.
+    #[serde(default)]
+    pub duration: Option<std::time::Duration>,
+}
+
+impl AudioChapter {
+    /// Chapter-list label: the title with the runtime appended when
+    /// known, e.g. "03 — 41:02". Unknown durations show the bare title.
+    pub fn display_title(&self) -> String {
+        match self.duration {
+            Some(duration) => format!("{} — {}", self.title, format_duration(duration)),
+            None => self.title.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextContent {
+    pub file: PathBuf,
+    pub format: TextFormat,
+    /// Further volumes of the same format in reading order, for books
+    /// split across `vol1.epub`/`vol2.epub`-style files. Empty for
+    /// ordinary single-file books.
+    #[serde(default)]
+    pub continuation: Vec<PathBuf>,
+}
+
+impl TextContent {
+    /// All text files of the book in reading order, the primary first.
+    pub fn files(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.file).chain(self.continuation.iter())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ebook {
+    pub id: EbookId,
+    pub title: String,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    /// Directory (or single file) the book was grouped from.
+    pub path: PathBuf,
+    pub audio_chapters: Vec<AudioChapter>,
+    pub text: Option<TextContent>,
+    /// Companion sync files (`.smil`/`.srt`/`.vtt`) found next to the
+    /// audio, importable into a `SyncMap` for read-along.
+    #[serde(default)]
+    pub sync_files: Vec<PathBuf>,
+    /// Series name parsed from a `Title (Series #N)` folder suffix.
+    /// `None` for standalone books.
+    #[serde(default)]
+    pub series: Option<String>,
+    /// Position within the series, when the suffix carried one.
+    #[serde(default)]
+    pub series_index: Option<u32>,
+    /// When the book appeared in the library, taken from the earliest file
+    /// mtime in the group at scan time. Serialized with the library cache
+    /// so it stays stable across restarts; `None` when the filesystem
+    /// reports nothing usable.
+    #[serde(default)]
+    pub added_at: Option<std::time::SystemTime>,
+}
+
+impl Ebook {
+    pub fn has_audio(&self) -> bool {
+        !self.audio_chapters.is_empty()
+    }
+
+    pub fn has_text(&self) -> bool {
+        self.text.is_some()
+    }
+
+    /// Short series badge for list rows, e.g. "The Expanse #3"; `None`
+    /// for standalone books so no badge is rendered.
+    pub fn series_label(&self) -> Option<String> {
+        let series = self.series.as_deref()?;
+        Some(match self.series_index {
+            Some(index) => format!("{series} #{index}"),
+            None => series.to_string(),
+        })
+    }
+
+    /// Whether every whitespace-separated word of `query` appears in the
+    /// book's title, author, or description, compared case- and
+    /// diacritic-insensitively. "bronte jane" matches "Jane Eyre" by
+    /// Charlotte Brontë.
+    pub fn matches(&self, query: &str) -> bool {
+        let haystack = normalize_for_match(&format!(
+            "{} {} {}",
+            self.title,
+            self.author.as_deref().unwrap_or(""),
+            self.description.as_deref().unwrap_or("")
+        ));
+        normalize_for_match(query)
+            .split_whitespace()
+            .all(|word| haystack.contains(word))
+    }
+}
+
+/// Media-type filter for the library view, composable with the search
+/// box: show everything, only books with audio, or only books with text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MediaFilter {
+    #[default]
+    All,
+    AudioOnly,
+    TextOnly,
+}
+
+impl MediaFilter {
+    pub fn matches(&self, book: &Ebook) -> bool {
+        match self {
+            MediaFilter::All => true,
+            MediaFilter::AudioOnly => book.has_audio(),
+            MediaFilter::TextOnly => book.has_text(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Title,
+    Author,
+    /// Most recently added first; books without `added_at` sort last.
+    Added,
+}
+
+pub fn sort_books(books: &mut [Ebook], key: SortKey) {
+    match key {
+        SortKey::Title => books.sort_by_key(|book| book.title.to_lowercase()),
+        SortKey::Author => books.sort_by_key(|book| {
+            (
+                book.author.as_deref().unwrap_or("").to_lowercase(),
+                book.title.to_lowercase(),
+            )
+        }),
+        SortKey::Added => books.sort_by_key(|book| {
+            (
+                std::cmp::Reverse(book.added_at),
+                book.title.to_lowercase(),
+            )
+        }),
+    }
+}
+
+/// What the grouped library view groups rows under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupKey {
+    Author,
+    Series,
+}
+
+/// One header-plus-rows section of the grouped library view.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookGroup {
+    pub label: String,
+    pub books: Vec<Ebook>,
+}
+
+/// Organize books under author or series headers, groups sorted by label
+/// and books within a series by `series_index`. Books without the
+/// grouping attribute land in a trailing catch-all group.
+pub fn group_books(books: &[Ebook], key: GroupKey) -> Vec<BookGroup> {
+    let label_for = |book: &Ebook| match key {
+        GroupKey::Author => book.author.clone(),
+        GroupKey::Series => book.series.clone(),
+    };
+
+    let mut groups: Vec<BookGroup> = Vec::new();
+    let mut ungrouped = Vec::new();
+    for book in books {
+        match label_for(book) {
+            Some(label) => {
+                match groups.iter_mut().find(|group| group.label == label) {
+                    Some(group) => group.books.push(book.clone()),
+                    None => groups.push(BookGroup {
+                        label,
+                        books: vec![book.clone()],
+                    }),
+                }
+            }
+            None => ungrouped.push(book.clone()),
+        }
+    }
+
+    groups.sort_by_key(|group| group.label.to_lowercase());
+    for group in &mut groups {
+        match key {
+            GroupKey::Author => sort_books(&mut group.books, SortKey::Title),
+            GroupKey::Series => group
+                .books
+                .sort_by_key(|book| (book.series_index, book.title.to_lowercase())),
+        }
+    }
+    if !ungrouped.is_empty() {
+        sort_books(&mut ungrouped, SortKey::Title);
+        groups.push(BookGroup {
+            label: match key {
+                GroupKey::Author => "Unknown author".to_string(),
+                GroupKey::Series => "Standalone".to_string(),
+            },
+            books: ungrouped,
+        });
+    }
+    groups
+}
+
+/// Lowercase and strip diacritics (NFD, dropping combining marks) so
+/// searches and filters share one matching rule.
+pub fn normalize_for_match(input: &str) -> String {
+    use unicode_normalization::char::is_combining_mark;
+    use unicode_normalization::UnicodeNormalization;
+
+    input
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct LibraryConfig {
+    pub root: PathBuf,
+    /// Maximum directory depth passed to the walker, counted from `root`
+    /// (depth 1 is a file directly under the root). Series subfolders
+    /// (`root/Author/Series/Book/files`) need at least depth 4, so the
+    /// default is generous but finite to keep a scan of an over-broad root
+    /// (say, a whole home directory) from descending forever.
+    pub max_depth: usize,
+    /// Ignore hidden entries (leading dot) and well-known OS cruft such as
+    /// `.DS_Store` or `Thumbs.db`. On by default; turn off to index
+    /// dotfiles deliberately.
+    pub skip_hidden: bool,
+    /// Follow symlinks while walking the root, so a library can be
+    /// assembled virtually from several locations. Off by default; when
+    /// enabled the walker's own loop detection guards against cycles.
+    pub follow_symlinks: bool,
+    /// Filename stems tried (in order) when looking for sibling cover
+    /// art; see [`cover::DEFAULT_COVER_PATTERNS`] for the defaults and
+    /// the `{title}` token.
+    pub cover_patterns: Vec<String>,
+    /// Merge an audio-only and a text-only entry with the same
+    /// normalized title and author into one dual-media book, for
+    /// libraries that keep a book's audio and EPUB in separate folders.
+    /// Off by default because the match is heuristic.
+    pub merge_split_books: bool,
+}
+
+/// Shared, thread-safe view of the scanned books. Cheap to clone; all
+/// clones observe the same contents, mirroring `EngineRegistryHandle`.
+#[derive(Clone, Default)]
+pub struct Library {
+    books: Arc<RwLock<Vec<Ebook>>>,
+}
+
+impl Library {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn iter(&self) -> Vec<Ebook> {
+        self.books.read().clone()
+    }
+
+    pub fn get(&self, id: &EbookId) -> Option<Ebook> {
+        self.books.read().iter().find(|book| &book.id == id).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.books.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.books.read().is_empty()
+    }
+
+    /// Books matching `query` under `Ebook::matches`.
+    pub fn search(&self, query: &str) -> Vec<Ebook> {
+        self.books
+            .read()
+            .iter()
+            .filter(|book| book.matches(query))
+            .cloned()
+            .collect()
+    }
+
+    /// Books passing both `filter` and (when non-empty) the metadata
+    /// search, so the media toggles and the search box compose.
+    pub fn filter(&self, query: &str, filter: MediaFilter) -> Vec<Ebook> {
+        self.books
+            .read()
+            .iter()
+            .filter(|book| filter.matches(book))
+            .filter(|book| query.trim().is_empty() || book.matches(query))
+            .cloned()
+            .collect()
+    }
+
+    /// Case-insensitive substring match on titles, returning owned clones
+    /// like `iter`.
+    pub fn find_by_title(&self, title: &str) -> Vec<Ebook> {
+        let needle = title.to_lowercase();
+        self.books
+            .read()
+            .iter()
+            .filter(|book| book.title.to_lowercase().contains(&needle))
+            .cloned()
+            .collect()
+    }
+
+    /// Case-insensitive substring match on authors; books without an
+    /// author never match.
+    pub fn find_by_author(&self, author: &str) -> Vec<Ebook> {
+        let needle = author.to_lowercase();
+        self.books
+            .read()
+            .iter()
+            .filter(|book| {
+                book.author
+                    .as_ref()
+                    .is_some_and(|a| a.to_lowercase().contains(&needle))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The next volume of `book`'s series: the sibling with the smallest
+    /// `series_index` above the current one, so gaps in a collection
+    /// skip forward rather than dead-ending. `None` for standalone
+    /// books, unnumbered volumes, and the end of the series.
+    pub fn next_in_series(&self, book: &Ebook) -> Option<Ebook> {
+        self.series_neighbor(book, |sibling, current| sibling > current)
+    }
+
+    /// The previous volume, mirroring [`Library::next_in_series`].
+    pub fn previous_in_series(&self, book: &Ebook) -> Option<Ebook> {
+        self.series_neighbor(book, |sibling, current| sibling < current)
+    }
+
+    fn series_neighbor(
+        &self,
+        book: &Ebook,
+        keep: impl Fn(u32, u32) -> bool,
+    ) -> Option<Ebook> {
+        let series = book.series.as_deref()?;
+        let current = book.series_index?;
+        self.books
+            .read()
+            .iter()
+            .filter(|sibling| {
+                sibling.id != book.id && sibling.series.as_deref() == Some(series)
+            })
+            .filter_map(|sibling| Some((sibling.series_index?, sibling)))
+            .filter(|(index, _)| keep(*index, current))
+            .min_by_key(|(index, _)| index.abs_diff(current))
+            .map(|(_, sibling)| sibling.clone())
+    }
+
+    /// Insert a book, keeping the title sort order. An existing entry with
+    /// the same id is replaced. Returns whether the contents changed.
+    pub fn insert(&self, ebook: Ebook) -> bool {
+        let mut books = self.books.write();
+        if let Some(existing) = books.iter_mut().find(|book| book.id == ebook.id) {
+            if *existing == ebook {
+                return false;
+            }
+            *existing = ebook;
+        } else {
+            let key = ebook.title.to_lowercase();
+            let at = books.partition_point(|book| book.title.to_lowercase() <= key);
+            books.insert(at, ebook);
+        }
+        true
+    }
+
+    /// Remove a book by id. Returns whether anything was removed.
+    pub fn remove(&self, id: &EbookId) -> bool {
+        let mut books = self.books.write();
+        let before = books.len();
+        books.retain(|book| &book.id != id);
+        books.len() != before
+    }
+
+    pub fn replace_all(&self, books: Vec<Ebook>) {
+        *self.books.write() = books;
+    }
+
+    /// Re-run the loader's scan and swap the contents in place. Readers
+    /// holding clones of individual `Ebook`s keep working across a reload.
+    pub fn reload(&self, loader: &LibraryLoader) -> Result<(), ScanError> {
+        loader.load_into(self)
+    }
+}
+
+/// Owns the scan configuration and populates a `Library` from disk.
+pub struct LibraryLoader {
+    config: LibraryConfig,
+}
+
+impl LibraryLoader {
+    pub fn new(config: LibraryConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &LibraryConfig {
+        &self.config
+    }
+
+    pub fn load(&self) -> Result<Vec<Ebook>, ScanError> {
+        scan_library(&self.config)
+    }
+
+    pub fn load_into(&self, library: &Library) -> Result<(), ScanError> {
+        let books = self.load()?;
+        library.replace_all(books);
+        Ok(())
+    }
+}
+
+impl LibraryConfig {
+    pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            skip_hidden: true,
+            follow_symlinks: false,
+            cover_patterns: cover::DEFAULT_COVER_PATTERNS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            merge_split_books: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::scan::tests::temp_root;
+    use std::fs;
+
+    #[test]
+    fn reload_picks_up_new_books() {
+        let root = temp_root("reload");
+        fs::write(root.join("first.epub"), b"x").unwrap();
+
+        let library = Library::new();
+        let loader = LibraryLoader::new(LibraryConfig::new(&root));
+        loader.load_into(&library).unwrap();
+        assert_eq!(library.len(), 1);
+
+        fs::write(root.join("second.epub"), b"x").unwrap();
+        library.reload(&loader).unwrap();
+        assert_eq!(library.len(), 2);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn lookups_match_case_insensitive_substrings() {
+        let root = temp_root("lookup");
+        let book = root.join("Charlotte Brontë/Jane Eyre");
+        fs::create_dir_all(&book).unwrap();
+        fs::write(book.join("jane.epub"), b"x").unwrap();
+
+        let library = Library::new();
+        LibraryLoader::new(LibraryConfig::new(&root))
+            .load_into(&library)
+            .unwrap();
+
+        assert_eq!(library.find_by_title("jane").len(), 1);
+        assert_eq!(library.find_by_title("EYRE").len(), 1);
+        assert!(library.find_by_title("austen").is_empty());
+        assert_eq!(library.find_by_author("brontë").len(), 1);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn matches_folds_diacritics_and_multi_word_queries() {
+        let book = Ebook {
+            id: EbookId("jane".into()),
+            title: "Jane Eyre".into(),
+            author: Some("Charlotte Brontë".into()),
+            description: None,
+            path: PathBuf::from("jane"),
+            audio_chapters: Vec::new(),
+            text: None,
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        };
+        assert!(book.matches("bronte jane"));
+        assert!(book.matches("EYRE"));
+        assert!(!book.matches("austen jane"));
+        assert_eq!(normalize_for_match("Café"), "cafe");
+    }
+
+    #[test]
+    fn grouping_sorts_series_by_index_and_collects_standalones() {
+        let book = |title: &str, series: Option<(&str, u32)>| Ebook {
+            id: EbookId(title.into()),
+            title: title.into(),
+            author: None,
+            description: None,
+            path: PathBuf::from(title),
+            audio_chapters: Vec::new(),
+            text: None,
+            sync_files: Vec::new(),
+            series: series.map(|(name, _)| name.to_string()),
+            series_index: series.map(|(_, index)| index),
+            added_at: None,
+        };
+        let books = vec![
+            book("Caliban's War", Some(("The Expanse", 2))),
+            book("Leviathan Wakes", Some(("The Expanse", 1))),
+            book("Standalone Novel", None),
+        ];
+
+        let groups = group_books(&books, GroupKey::Series);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].label, "The Expanse");
+        assert_eq!(
+            groups[0].books.iter().map(|b| b.title.as_str()).collect::<Vec<_>>(),
+            vec!["Leviathan Wakes", "Caliban's War"]
+        );
+        assert_eq!(groups[1].label, "Standalone");
+        assert_eq!(groups[1].books.len(), 1);
+    }
+
+    #[test]
+    fn series_navigation_skips_gaps_and_stops_at_the_ends() {
+        let book = |title: &str, series: Option<(&str, u32)>| Ebook {
+            id: EbookId(title.into()),
+            title: title.into(),
+            author: None,
+            description: None,
+            path: PathBuf::from(title),
+            audio_chapters: Vec::new(),
+            text: None,
+            sync_files: Vec::new(),
+            series: series.map(|(name, _)| name.to_string()),
+            series_index: series.map(|(_, index)| index),
+            added_at: None,
+        };
+        let first = book("Leviathan Wakes", Some(("The Expanse", 1)));
+        // Volume 2 is missing from the library; 3 is the next owned one.
+        let third = book("Abaddon's Gate", Some(("The Expanse", 3)));
+        let standalone = book("Standalone Novel", None);
+        let library = Library::new();
+        library.replace_all(vec![first.clone(), third.clone(), standalone.clone()]);
+
+        assert_eq!(
+            library.next_in_series(&first).map(|b| b.title),
+            Some("Abaddon's Gate".to_string())
+        );
+        assert_eq!(
+            library.previous_in_series(&third).map(|b| b.title),
+            Some("Leviathan Wakes".to_string())
+        );
+        assert_eq!(library.previous_in_series(&first), None);
+        assert_eq!(library.next_in_series(&standalone), None);
+    }
+
+    #[test]
+    fn media_filter_composes_with_search() {
+        let root = temp_root("filter");
+        fs::write(root.join("Novel.epub"), b"x").unwrap();
+        fs::write(root.join("Audiobook.mp3"), b"x").unwrap();
+
+        let library = Library::new();
+        LibraryLoader::new(LibraryConfig::new(&root))
+            .load_into(&library)
+            .unwrap();
+
+        assert_eq!(library.filter("", MediaFilter::All).len(), 2);
+        let audio = library.filter("", MediaFilter::AudioOnly);
+        assert_eq!(audio.len(), 1);
+        assert!(audio[0].has_audio());
+        assert_eq!(library.filter("novel", MediaFilter::TextOnly).len(), 1);
+        assert!(library.filter("novel", MediaFilter::AudioOnly).is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn insert_keeps_sort_order_and_remove_reports_change() {
+        let root = temp_root("insert");
+        fs::write(root.join("Alpha.epub"), b"x").unwrap();
+        fs::write(root.join("Gamma.epub"), b"x").unwrap();
+
+        let library = Library::new();
+        LibraryLoader::new(LibraryConfig::new(&root))
+            .load_into(&library)
+            .unwrap();
+
+        let mut beta = library.get(&library.iter()[0].id).unwrap();
+        beta.id = EbookId("beta".into());
+        beta.title = "Beta".into();
+        assert!(library.insert(beta.clone()));
+        assert!(!library.insert(beta.clone()));
+        let titles: Vec<String> = library.iter().into_iter().map(|b| b.title).collect();
+        assert_eq!(titles, ["Alpha", "Beta", "Gamma"]);
+
+        assert!(library.remove(&beta.id));
+        assert!(!library.remove(&beta.id));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
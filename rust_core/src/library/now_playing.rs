@@ -0,0 +1,68 @@
+//! Shared "which book is playing" state for library views.
+
+use parking_lot::RwLock;
+
+use super::EbookId;
+
+/// Tracks the currently playing book so library rows can show a playing
+/// indicator. Fed from chapter start/stop notifications; queried on
+/// repaint. Cheap to share behind an `Arc`.
+#[derive(Default)]
+pub struct NowPlaying {
+    current: RwLock<Option<(EbookId, usize)>>,
+}
+
+impl NowPlaying {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A chapter of `book` started playing. Replaces any previous entry,
+    /// so switching books moves the indicator rather than duplicating it.
+    pub fn on_chapter_started(&self, book: EbookId, chapter: usize) {
+        *self.current.write() = Some((book, chapter));
+    }
+
+    /// Playback stopped (or the last chapter completed with nothing
+    /// queued); no book shows as playing.
+    pub fn on_stopped(&self) {
+        *self.current.write() = None;
+    }
+
+    /// Whether `book` is the one currently playing.
+    pub fn is_playing(&self, book: &EbookId) -> bool {
+        self.current
+            .read()
+            .as_ref()
+            .is_some_and(|(current, _)| current == book)
+    }
+
+    pub fn current(&self) -> Option<(EbookId, usize)> {
+        self.current.read().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indicator_follows_the_playing_book_and_clears_on_stop() {
+        let now_playing = NowPlaying::new();
+        let first = EbookId("a".into());
+        let second = EbookId("b".into());
+        assert!(!now_playing.is_playing(&first));
+
+        now_playing.on_chapter_started(first.clone(), 0);
+        assert!(now_playing.is_playing(&first));
+
+        // Switching books moves the indicator.
+        now_playing.on_chapter_started(second.clone(), 3);
+        assert!(!now_playing.is_playing(&first));
+        assert_eq!(now_playing.current(), Some((second.clone(), 3)));
+
+        now_playing.on_stopped();
+        assert!(!now_playing.is_playing(&second));
+        assert_eq!(now_playing.current(), None);
+    }
+}
@@ -0,0 +1,556 @@
+//! Filesystem scan that groups audio/text files under a root into `Ebook`s.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use super::{AudioChapter, Ebook, EbookId, LibraryConfig, MetadataCache, TextContent, TextFormat};
+
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "m4a", "m4b", "ogg", "opus", "flac", "wav"];
+
+/// Companion alignment files associated with a book for read-along.
+const SYNC_EXTENSIONS: &[&str] = &["smil", "srt", "vtt"];
+
+/// OS metadata files that are never book content, even when not dot-prefixed.
+const SYSTEM_FILE_DENYLIST: &[&str] = &["Thumbs.db", "desktop.ini", "ehthumbs.db"];
+
+#[derive(Debug, Error)]
+pub enum ScanError {
+    #[error("library root {0} is not a directory")]
+    RootMissing(PathBuf),
+    #[error("walk failed: {0}")]
+    Walk(#[from] walkdir::Error),
+}
+
+pub fn scan_library(config: &LibraryConfig) -> Result<Vec<Ebook>, ScanError> {
+    scan_library_with_cache(config, None)
+}
+
+/// Like [`scan_library`], consulting `cache` for per-file tag data so
+/// warm scans skip re-opening unchanged audio files.
+pub fn scan_library_with_cache(
+    config: &LibraryConfig,
+    cache: Option<&MetadataCache>,
+) -> Result<Vec<Ebook>, ScanError> {
+    if !config.root.is_dir() {
+        return Err(ScanError::RootMissing(config.root.clone()));
+    }
+
+    // Group key -> files, ordered so chapter indices are stable.
+    let mut groups: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+    let skip_hidden = config.skip_hidden;
+    let walker = WalkDir::new(&config.root)
+        .max_depth(config.max_depth)
+        .follow_links(config.follow_symlinks)
+        .into_iter()
+        .filter_entry(move |entry| {
+            !skip_hidden || entry.depth() == 0 || !is_hidden_or_system(entry.file_name())
+        });
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        if !is_audio_file(path) && !is_sync_file(path) && effective_text_format(path).is_none() {
+            continue;
+        }
+        let key = derive_group_key(&config.root, path);
+        groups.entry(key).or_default().push(path.to_path_buf());
+    }
+
+    let mut books: Vec<Ebook> = groups
+        .into_iter()
+        .filter_map(|(key, files)| build_ebook(&config.root, key, files, cache))
+        .collect();
+    if config.merge_split_books {
+        merge_split_books(&mut books);
+    }
+    books.sort_by_key(|book| book.title.to_lowercase());
+    Ok(books)
+}
+
+/// Fold text-only entries into an audio-only entry of the same book, for
+/// libraries that keep the audio and the EPUB in separate folders. The
+/// match requires equal normalized titles and equal authors (or both
+/// missing), so "Dune" by Herbert never absorbs "Dune" by someone else.
+fn merge_split_books(books: &mut Vec<Ebook>) {
+    use super::normalize_for_match;
+
+    let match_key = |book: &Ebook| {
+        (
+            normalize_for_match(&book.title),
+            book.author.as_deref().map(normalize_for_match),
+        )
+    };
+
+    let mut merged: Vec<Ebook> = Vec::with_capacity(books.len());
+    for book in books.drain(..) {
+        let counterpart = merged.iter_mut().find(|existing| {
+            // Only a clean audio/text split merges; dual-media entries
+            // and same-media duplicates stay separate.
+            existing.has_audio() != book.has_audio()
+                && existing.has_text() != book.has_text()
+                && match_key(existing) == match_key(&book)
+        });
+        let Some(existing) = counterpart else {
+            merged.push(book);
+            continue;
+        };
+        let (audio, text) = if existing.has_audio() {
+            (existing, book)
+        } else {
+            (&mut *existing, book)
+        };
+        if audio.has_audio() {
+            audio.text = text.text;
+        } else {
+            // `audio` is actually the text half; take the other's audio.
+            audio.audio_chapters = text.audio_chapters;
+        }
+        audio.sync_files.extend(text.sync_files);
+        audio.series = audio.series.take().or(text.series);
+        audio.series_index = audio.series_index.or(text.series_index);
+        audio.description = audio.description.take().or(text.description);
+        audio.added_at = match (audio.added_at, text.added_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+    *books = merged;
+}
+
+/// Files directly under the root are each their own book; anything deeper is
+/// grouped by its containing directory.
+pub(crate) fn derive_group_key(root: &Path, file: &Path) -> PathBuf {
+    match file.parent() {
+        Some(parent) if parent != root => parent.to_path_buf(),
+        _ => file.to_path_buf(),
+    }
+}
+
+fn build_ebook(
+    root: &Path,
+    key: PathBuf,
+    mut files: Vec<PathBuf>,
+    cache: Option<&MetadataCache>,
+) -> Option<Ebook> {
+    files.sort();
+
+    let mut audio_chapters = Vec::new();
+    let mut text: Option<TextContent> = None;
+    let mut sync_files = Vec::new();
+    let mut added_at: Option<std::time::SystemTime> = None;
+    for file in &files {
+        if let Some(mtime) = file_mtime(file) {
+            added_at = Some(match added_at {
+                Some(earliest) => earliest.min(mtime),
+                None => mtime,
+            });
+        }
+        if is_sync_file(file) {
+            sync_files.push(file.clone());
+        } else if is_audio_file(file) {
+            let (track_number, duration) = match cache {
+                Some(cache) => {
+                    let metadata = cache.audio_metadata(file);
+                    (metadata.track_number, metadata.duration())
+                }
+                None => (read_track_number(file), read_audio_duration(file)),
+            };
+            audio_chapters.push(AudioChapter {
+                chapter_index: audio_chapters.len(),
+                title: file_stem(file),
+                file: file.clone(),
+                track_number,
+                duration,
+            });
+        } else if let Some(format) = effective_text_format(file) {
+            match &mut text {
+                // Prefer the richest format when a folder holds several.
+                Some(existing) if format_rank(format) > format_rank(existing.format) => {}
+                Some(existing) if format_rank(format) == format_rank(existing.format) => {
+                    // Same format, later in sort order: a further volume
+                    // of the same book.
+                    existing.continuation.push(file.clone());
+                }
+                _ => {
+                    text = Some(TextContent {
+                        file: file.clone(),
+                        format,
+                        continuation: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+    if audio_chapters.is_empty() && text.is_none() {
+        return None;
+    }
+
+    let (title, series, series_index) = split_series_suffix(&file_stem(&key));
+    let author = key
+        .parent()
+        .filter(|parent| *parent != root && parent.starts_with(root))
+        .map(file_stem);
+    let id = EbookId::from_path(key.strip_prefix(root).unwrap_or(&key));
+    Some(Ebook {
+        id,
+        title,
+        author,
+        description: None,
+        path: key,
+        audio_chapters,
+        text,
+        sync_files,
+        series,
+        series_index,
+        added_at,
+    })
+}
+
+/// Split a `Title (Series #N)` folder name into title, series, and index.
+/// Names without that suffix come back unchanged with no series.
+fn split_series_suffix(name: &str) -> (String, Option<String>, Option<u32>) {
+    let trimmed = name.trim_end();
+    let Some(open) = trimmed.rfind('(').filter(|_| trimmed.ends_with(')')) else {
+        return (name.to_string(), None, None);
+    };
+    let inner = &trimmed[open + 1..trimmed.len() - 1];
+    let title = trimmed[..open].trim_end();
+    let (series, index) = match inner.rsplit_once('#') {
+        Some((series, digits)) => match digits.trim().parse::<u32>() {
+            Ok(index) => (series.trim_end(), Some(index)),
+            Err(_) => return (name.to_string(), None, None),
+        },
+        // A bare parenthetical isn't reliably a series name; leave it.
+        None => return (name.to_string(), None, None),
+    };
+    if title.is_empty() || series.is_empty() {
+        return (name.to_string(), None, None);
+    }
+    (title.to_string(), Some(series.to_string()), index)
+}
+
+/// Earliest usable mtime; filesystems that report errors or nonsense
+/// (before the Unix epoch) yield `None`.
+fn file_mtime(path: &Path) -> Option<std::time::SystemTime> {
+    let mtime = std::fs::metadata(path).ok()?.modified().ok()?;
+    if mtime < std::time::SystemTime::UNIX_EPOCH {
+        return None;
+    }
+    Some(mtime)
+}
+
+/// Track number from the file's tags; unreadable or untagged files yield
+/// `None` rather than failing the scan.
+fn read_track_number(path: &Path) -> Option<u32> {
+    use lofty::file::TaggedFileExt;
+    use lofty::tag::Accessor;
+
+    let tagged = lofty::read_from_path(path).ok()?;
+    tagged
+        .primary_tag()
+        .or_else(|| tagged.first_tag())
+        .and_then(|tag| tag.track())
+}
+
+/// Runtime from the file's audio headers; unreadable files yield `None`
+/// rather than failing the scan.
+fn read_audio_duration(path: &Path) -> Option<std::time::Duration> {
+    use lofty::file::AudioFile;
+
+    let tagged = lofty::read_from_path(path).ok()?;
+    let duration = tagged.properties().duration();
+    (!duration.is_zero()).then_some(duration)
+}
+
+fn is_hidden_or_system(name: &std::ffi::OsStr) -> bool {
+    let name = name.to_string_lossy();
+    name.starts_with('.') || SYSTEM_FILE_DENYLIST.contains(&name.as_ref())
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string_lossy().into_owned())
+}
+
+pub(crate) fn file_extension(path: &Path) -> Option<String> {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    file_extension(path)
+        .map(|ext| AUDIO_EXTENSIONS.contains(&ext.as_str()))
+        .unwrap_or(false)
+}
+
+fn is_sync_file(path: &Path) -> bool {
+    file_extension(path)
+        .map(|ext| SYNC_EXTENSIONS.contains(&ext.as_str()))
+        .unwrap_or(false)
+}
+
+/// Classification that trusts the extension when the contents agree, but
+/// falls back to (or is corrected by) a magic-byte sniff for extensionless
+/// and mislabeled files — an HTML page saved as `.txt`, a PDF with no
+/// extension, and so on.
+fn effective_text_format(path: &Path) -> Option<TextFormat> {
+    let by_extension = text_format_for(path);
+    match (by_extension, sniff_text_format(path)) {
+        (Some(ext), None) => Some(ext),
+        (Some(ext), Some(sniffed)) if sniffed == ext => Some(ext),
+        // Zip-based containers: an `.epub` legitimately sniffs as zip.
+        (Some(TextFormat::Epub), Some(_)) => Some(TextFormat::Epub),
+        (_, Some(sniffed)) => Some(sniffed),
+        (None, None) => None,
+    }
+}
+
+/// Lightweight magic-byte sniff over the first bytes of the file.
+fn sniff_text_format(path: &Path) -> Option<TextFormat> {
+    use std::io::Read;
+
+    let mut head = [0u8; 512];
+    let mut file = std::fs::File::open(path).ok()?;
+    let read = file.read(&mut head).ok()?;
+    let head = &head[..read];
+    if head.starts_with(b"PK\x03\x04") {
+        return Some(TextFormat::Epub);
+    }
+    if head.starts_with(b"%PDF") {
+        return Some(TextFormat::Pdf);
+    }
+    let text = String::from_utf8_lossy(head).to_lowercase();
+    let trimmed = text.trim_start();
+    if trimmed.starts_with("<!doctype html") || trimmed.starts_with("<html") {
+        return Some(TextFormat::Html);
+    }
+    None
+}
+
+fn text_format_for(path: &Path) -> Option<TextFormat> {
+    match file_extension(path)?.as_str() {
+        "epub" => Some(TextFormat::Epub),
+        "pdf" => Some(TextFormat::Pdf),
+        "md" | "markdown" => Some(TextFormat::Markdown),
+        "html" | "htm" | "xhtml" => Some(TextFormat::Html),
+        "txt" => Some(TextFormat::PlainText),
+        _ => None,
+    }
+}
+
+fn format_rank(format: TextFormat) -> u8 {
+    match format {
+        TextFormat::Epub => 0,
+        TextFormat::Markdown => 1,
+        TextFormat::Html => 2,
+        TextFormat::Pdf => 3,
+        TextFormat::PlainText => 4,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::fs;
+
+    pub(crate) fn temp_root(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rust_core_scan_{tag}_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn groups_folder_files_into_one_book() {
+        let root = temp_root("group");
+        let book = root.join("Author/The Book");
+        fs::create_dir_all(&book).unwrap();
+        fs::write(book.join("01.mp3"), b"x").unwrap();
+        fs::write(book.join("02.mp3"), b"x").unwrap();
+        fs::write(book.join("book.epub"), b"x").unwrap();
+
+        let books = scan_library(&LibraryConfig::new(&root)).unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].title, "The Book");
+        assert_eq!(books[0].author.as_deref(), Some("Author"));
+        assert_eq!(books[0].audio_chapters.len(), 2);
+        assert!(books[0].has_text());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn associates_companion_sync_files_with_the_book() {
+        let root = temp_root("syncfiles");
+        let book = root.join("Narrated");
+        fs::create_dir_all(&book).unwrap();
+        fs::write(book.join("01.mp3"), b"x").unwrap();
+        fs::write(book.join("01.srt"), b"1\n00:00:01,000 --> 00:00:02,000\nHi.\n").unwrap();
+
+        let books = scan_library(&LibraryConfig::new(&root)).unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].sync_files.len(), 1);
+        assert!(books[0].sync_files[0].ends_with("01.srt"));
+        // The subtitle file is not misclassified as book text.
+        assert!(!books[0].has_text());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn same_format_volumes_stay_one_book_in_order() {
+        let root = temp_root("volumes");
+        let book = root.join("Box Set");
+        fs::create_dir_all(&book).unwrap();
+        fs::write(book.join("vol2.epub"), b"x").unwrap();
+        fs::write(book.join("vol1.epub"), b"x").unwrap();
+        fs::write(book.join("notes.txt"), b"x").unwrap();
+
+        let books = scan_library(&LibraryConfig::new(&root)).unwrap();
+        assert_eq!(books.len(), 1);
+        let text = books[0].text.as_ref().unwrap();
+        // The poorer-format extra file is ignored; epub volumes chain.
+        assert!(text.file.ends_with("vol1.epub"));
+        assert_eq!(text.continuation.len(), 1);
+        assert!(text.continuation[0].ends_with("vol2.epub"));
+        assert_eq!(text.files().count(), 2);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn split_audio_and_text_folders_merge_only_when_opted_in() {
+        let root = temp_root("merge");
+        let audio = root.join("Frank Herbert/Dune");
+        fs::create_dir_all(&audio).unwrap();
+        fs::write(audio.join("01.mp3"), b"x").unwrap();
+        let text = root.join("Frank Herbert (epubs)/Dune");
+        fs::create_dir_all(&text).unwrap();
+        fs::write(text.join("dune.epub"), b"x").unwrap();
+
+        // Default: two entries, since the match is heuristic.
+        let books = scan_library(&LibraryConfig::new(&root)).unwrap();
+        assert_eq!(books.len(), 2);
+
+        let mut config = LibraryConfig::new(&root);
+        config.merge_split_books = true;
+        let books = scan_library(&config).unwrap();
+        // Authors differ ("Frank Herbert" vs "Frank Herbert (epubs)"),
+        // so still no merge.
+        assert_eq!(books.len(), 2);
+
+        fs::rename(
+            root.join("Frank Herbert (epubs)"),
+            root.join("frank herbert"),
+        )
+        .unwrap();
+        let books = scan_library(&config).unwrap();
+        assert_eq!(books.len(), 1);
+        assert!(books[0].has_audio());
+        assert!(books[0].has_text());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn parses_series_suffix_from_folder_names() {
+        let root = temp_root("series");
+        let book = root.join("Author/Leviathan Wakes (The Expanse #1)");
+        fs::create_dir_all(&book).unwrap();
+        fs::write(book.join("book.epub"), b"x").unwrap();
+        let plain = root.join("Author/Standalone (annotated)");
+        fs::create_dir_all(&plain).unwrap();
+        fs::write(plain.join("book.epub"), b"x").unwrap();
+
+        let books = scan_library(&LibraryConfig::new(&root)).unwrap();
+        assert_eq!(books.len(), 2);
+        assert_eq!(books[0].title, "Leviathan Wakes");
+        assert_eq!(books[0].series.as_deref(), Some("The Expanse"));
+        assert_eq!(books[0].series_index, Some(1));
+        assert_eq!(books[0].series_label().as_deref(), Some("The Expanse #1"));
+        // A bare parenthetical is not treated as a series.
+        assert_eq!(books[1].title, "Standalone (annotated)");
+        assert_eq!(books[1].series, None);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn sniffs_extensionless_and_mislabeled_files() {
+        let root = temp_root("sniff");
+        let book = root.join("Mystery");
+        fs::create_dir_all(&book).unwrap();
+        fs::write(book.join("scan"), b"%PDF-1.7 ...").unwrap();
+        let page = root.join("Page");
+        fs::create_dir_all(&page).unwrap();
+        fs::write(page.join("page.txt"), b"<!DOCTYPE html><html><body>hi</body></html>").unwrap();
+
+        let books = scan_library(&LibraryConfig::new(&root)).unwrap();
+        assert_eq!(books.len(), 2);
+        let formats: Vec<TextFormat> = books
+            .iter()
+            .map(|b| b.text.as_ref().unwrap().format)
+            .collect();
+        assert!(formats.contains(&TextFormat::Pdf));
+        assert!(formats.contains(&TextFormat::Html));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn skips_hidden_files_unless_opted_out() {
+        let root = temp_root("hidden");
+        fs::write(root.join(".hidden.epub"), b"x").unwrap();
+        fs::write(root.join("Thumbs.db"), b"x").unwrap();
+        fs::write(root.join("visible.epub"), b"x").unwrap();
+
+        let config = LibraryConfig::new(&root);
+        let books = scan_library(&config).unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].title, "visible");
+
+        let mut config = LibraryConfig::new(&root);
+        config.skip_hidden = false;
+        let books = scan_library(&config).unwrap();
+        assert_eq!(books.len(), 2);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinks_only_when_configured() {
+        let root = temp_root("symlink");
+        let external = temp_root("symlink_target");
+        fs::write(external.join("linked.epub"), b"x").unwrap();
+        std::os::unix::fs::symlink(&external, root.join("shelf")).unwrap();
+
+        let books = scan_library(&LibraryConfig::new(&root)).unwrap();
+        assert!(books.is_empty());
+
+        let mut config = LibraryConfig::new(&root);
+        config.follow_symlinks = true;
+        let books = scan_library(&config).unwrap();
+        assert_eq!(books.len(), 1);
+        let _ = fs::remove_dir_all(&root);
+        let _ = fs::remove_dir_all(&external);
+    }
+
+    #[test]
+    fn max_depth_limits_traversal() {
+        let root = temp_root("depth");
+        let deep = root.join("a/b/c/d");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("deep.epub"), b"x").unwrap();
+        fs::write(root.join("shallow.txt"), b"x").unwrap();
+
+        let mut config = LibraryConfig::new(&root);
+        config.max_depth = 2;
+        let books = scan_library(&config).unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].title, "shallow");
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,149 @@
+//! Online phrase lookup against Wikipedia's REST summary endpoint, for
+//! names and concepts the offline dictionary won't cover.
+//!
+//! Like PDF cover rendering, the transport is injected: the core builds
+//! the request URL, parses the response, and debounces, while the
+//! frontend supplies the HTTP stack it already has. Transport errors
+//! (offline, timeout) come back as plain strings fit for the popup.
+//! Only compiled in behind the `wikipedia` feature.
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Deserialize;
+
+/// What the lookup popup shows: the resolved article title, a short
+/// extract, and the canonical page URL for a "read more" link.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PhraseSummary {
+    pub title: String,
+    pub extract: String,
+    #[serde(rename = "content_urls", deserialize_with = "desktop_page_url")]
+    pub url: String,
+}
+
+/// The REST summary URL for `phrase`, percent-encoded per RFC 3986
+/// unreserved characters (spaces become underscores first, matching
+/// Wikipedia's title convention).
+pub fn summary_url(phrase: &str) -> String {
+    let title = phrase.trim().replace(' ', "_");
+    let mut encoded = String::with_capacity(title.len());
+    for byte in title.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    format!("https://en.wikipedia.org/api/rest_v1/page/summary/{encoded}")
+}
+
+/// Fetch and parse the summary for `phrase`. `fetch` performs the HTTP
+/// GET and returns the response body, or a user-facing error for
+/// offline/timeout cases.
+pub fn lookup_phrase(
+    phrase: &str,
+    fetch: impl Fn(&str) -> Result<String, String>,
+) -> Result<PhraseSummary, String> {
+    let body = fetch(&summary_url(phrase))?;
+    parse_summary(&body)
+}
+
+/// Parse a REST summary response body.
+pub fn parse_summary(body: &str) -> Result<PhraseSummary, String> {
+    serde_json::from_str(body).map_err(|err| format!("unexpected response: {err}"))
+}
+
+fn desktop_page_url<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct ContentUrls {
+        desktop: PageUrls,
+    }
+    #[derive(Deserialize)]
+    struct PageUrls {
+        page: String,
+    }
+    Ok(ContentUrls::deserialize(deserializer)?.desktop.page)
+}
+
+/// Swallows lookups that arrive faster than `interval`, so dragging a
+/// selection across the page doesn't fire a request per mouse move;
+/// only the selection the user settles on goes out.
+pub struct LookupDebouncer {
+    interval: Duration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl LookupDebouncer {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Whether a lookup may fire now; firing arms the debounce window.
+    pub fn ready(&self) -> bool {
+        let mut last = self.last.lock();
+        let now = Instant::now();
+        match *last {
+            Some(fired) if now.duration_since(fired) < self.interval => false,
+            _ => {
+                *last = Some(now);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_encode_titles_the_wikipedia_way() {
+        assert_eq!(
+            summary_url("Moby Dick"),
+            "https://en.wikipedia.org/api/rest_v1/page/summary/Moby_Dick"
+        );
+        assert_eq!(
+            summary_url("Brontë"),
+            "https://en.wikipedia.org/api/rest_v1/page/summary/Bront%C3%AB"
+        );
+    }
+
+    #[test]
+    fn summaries_parse_and_transport_errors_pass_through() {
+        let body = r#"{
+            "title": "Moby-Dick",
+            "extract": "Moby-Dick is an 1851 novel by Herman Melville.",
+            "content_urls": {
+                "desktop": {"page": "https://en.wikipedia.org/wiki/Moby-Dick"},
+                "mobile": {"page": "https://en.m.wikipedia.org/wiki/Moby-Dick"}
+            }
+        }"#;
+        let summary = lookup_phrase("Moby Dick", |_| Ok(body.to_string())).unwrap();
+        assert_eq!(summary.title, "Moby-Dick");
+        assert_eq!(summary.url, "https://en.wikipedia.org/wiki/Moby-Dick");
+
+        assert_eq!(
+            lookup_phrase("Moby Dick", |_| Err("offline".into())),
+            Err("offline".into())
+        );
+        assert!(parse_summary("not json").is_err());
+    }
+
+    #[test]
+    fn debouncer_swallows_rapid_lookups() {
+        let debouncer = LookupDebouncer::new(Duration::from_secs(60));
+        assert!(debouncer.ready());
+        assert!(!debouncer.ready());
+        let instant = LookupDebouncer::new(Duration::ZERO);
+        assert!(instant.ready());
+        assert!(instant.ready());
+    }
+}
@@ -0,0 +1,1531 @@
+//! SQLite-backed persistence for reading positions and related state.
+
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::library::EbookId;
+
+#[derive(Debug, Error)]
+pub enum PersistenceError {
+    #[error("no usable data directory for the progress database")]
+    NoDataDir,
+    #[error("database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Position within a book: chapter, sentence within the chapter, word
+/// within the sentence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReaderPosition {
+    pub chapter: usize,
+    pub sentence: usize,
+    pub word: usize,
+}
+
+/// A saved spot in a book, optionally annotated with a short note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub id: i64,
+    pub book_id: EbookId,
+    pub chapter: usize,
+    pub sentence: usize,
+    pub note: Option<String>,
+}
+
+/// A highlighted passage, optionally annotated. Unlike a bookmark it
+/// carries the highlighted text itself, so exports and the highlights
+/// list stay meaningful even if the book's text is re-extracted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    pub id: i64,
+    pub book_id: EbookId,
+    pub chapter: usize,
+    pub sentence: usize,
+    pub text: String,
+    pub note: Option<String>,
+    pub color: HighlightColor,
+}
+
+/// Highlight colors, picked at creation time so study workflows can
+/// categorize (definitions vs. questions vs. quotes). Stored as text;
+/// unknown stored values fall back to yellow rather than erroring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HighlightColor {
+    #[default]
+    Yellow,
+    Green,
+    Blue,
+    Pink,
+}
+
+impl HighlightColor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HighlightColor::Yellow => "yellow",
+            HighlightColor::Green => "green",
+            HighlightColor::Blue => "blue",
+            HighlightColor::Pink => "pink",
+        }
+    }
+
+    fn from_stored(value: &str) -> Self {
+        match value {
+            "green" => HighlightColor::Green,
+            "blue" => HighlightColor::Blue,
+            "pink" => HighlightColor::Pink,
+            _ => HighlightColor::Yellow,
+        }
+    }
+}
+
+/// The engine and voice a book was last narrated with, so reopening it
+/// resumes with the same narration regardless of the current
+/// environment defaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoicePreference {
+    /// Engine identifier, e.g. a model path for piper.
+    pub engine: String,
+    /// Engine-specific voice/speaker id, when the engine has more than
+    /// one.
+    pub voice: Option<String>,
+}
+
+/// One reader-window open/close pair. `ended_at` is `None` while the
+/// session is still open (or if the app died before closing it).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadingSession {
+    pub id: i64,
+    pub book_id: EbookId,
+    pub started_at: i64,
+    pub ended_at: Option<i64>,
+}
+
+/// Shared handle over the progress database. Writes are small and
+/// serialized through a mutex, cheap enough to run from close handlers
+/// and shutdown paths without stalling them.
+pub struct Database {
+    conn: Mutex<Connection>,
+}
+
+impl Database {
+    /// Open (or create) the default database under the app data dir,
+    /// recovering from a corrupt file rather than failing.
+    pub fn open_default() -> Result<Self, PersistenceError> {
+        Self::open_or_recover(&db_path()?)
+    }
+
+    /// Open a database, and if the file turns out to be corrupt (power
+    /// loss mid-write is the usual cause), move it aside as
+    /// `<name>.corrupt` and start over with a fresh schema so the app
+    /// stays usable — only the damaged progress is lost.
+    pub fn open_or_recover(path: &Path) -> Result<Self, PersistenceError> {
+        match Self::open_at(path) {
+            Ok(db) if db.integrity_ok() => Ok(db),
+            Ok(_) | Err(PersistenceError::Sqlite(_)) => {
+                let backup = path.with_extension("sqlite.corrupt");
+                let _ = std::fs::rename(path, &backup);
+                warn!(
+                    path = %path.display(),
+                    backup = %backup.display(),
+                    "progress database was corrupt; moved aside and recreated"
+                );
+                Self::open_at(path)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn integrity_ok(&self) -> bool {
+        self.conn
+            .lock()
+            .query_row("PRAGMA integrity_check", [], |row| {
+                row.get::<_, String>(0)
+            })
+            .map(|result| result == "ok")
+            .unwrap_or(false)
+    }
+
+    /// Open a connection that lives purely in memory — nothing touches the
+    /// filesystem and no state is shared between instances, which is what
+    /// unit tests want.
+    pub fn open_in_memory() -> Result<Self, PersistenceError> {
+        let db = Self {
+            conn: Mutex::new(Connection::open_in_memory()?),
+        };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    /// Open a database at an explicit path, creating parent directories as
+    /// needed.
+    pub fn open_at(path: &Path) -> Result<Self, PersistenceError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)?;
+        let db = Self {
+            conn: Mutex::new(conn),
+        };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    fn init_schema(&self) -> Result<(), PersistenceError> {
+        self.conn.lock().execute_batch(
+            "CREATE TABLE IF NOT EXISTS reader_progress (
+                book_id TEXT PRIMARY KEY,
+                chapter INTEGER NOT NULL,
+                sentence INTEGER NOT NULL,
+                word INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS bookmarks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                book_id TEXT NOT NULL,
+                chapter INTEGER NOT NULL,
+                sentence INTEGER NOT NULL,
+                note TEXT,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS highlights (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                book_id TEXT NOT NULL,
+                chapter INTEGER NOT NULL,
+                sentence INTEGER NOT NULL,
+                text TEXT NOT NULL,
+                note TEXT,
+                color TEXT NOT NULL DEFAULT 'yellow',
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS reading_sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                book_id TEXT NOT NULL,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER
+            );
+            CREATE TABLE IF NOT EXISTS chapter_progress (
+                book_id TEXT NOT NULL,
+                chapter INTEGER NOT NULL,
+                sentence INTEGER NOT NULL,
+                word INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                PRIMARY KEY (book_id, chapter)
+            );
+            CREATE TABLE IF NOT EXISTS book_voices (
+                book_id TEXT PRIMARY KEY,
+                engine TEXT NOT NULL,
+                voice TEXT,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS sync_maps (
+                book_id TEXT NOT NULL,
+                chapter INTEGER NOT NULL,
+                audio_mtime INTEGER NOT NULL,
+                points TEXT NOT NULL,
+                PRIMARY KEY (book_id, chapter)
+            );",
+        )?;
+        // Databases created before highlights had colors gain the
+        // column in place; the error on re-run is the column already
+        // existing, which is fine.
+        let _ = self.conn.lock().execute(
+            "ALTER TABLE highlights ADD COLUMN color TEXT NOT NULL DEFAULT 'yellow'",
+            [],
+        );
+        Ok(())
+    }
+
+    /// Upsert the current position for a book. Called on chapter change,
+    /// sentence seek, TTS finalize, and window close so a hard exit loses
+    /// nothing. Also records the per-chapter position, so coming back to
+    /// this chapter later restores it even after reading elsewhere.
+    pub fn save_progress(
+        &self,
+        book_id: &EbookId,
+        position: ReaderPosition,
+    ) -> Result<(), PersistenceError> {
+        let conn = self.conn.lock();
+        let now = unix_now();
+        conn.execute(
+            "INSERT INTO reader_progress (book_id, chapter, sentence, word, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(book_id) DO UPDATE SET
+                chapter = excluded.chapter,
+                sentence = excluded.sentence,
+                word = excluded.word,
+                updated_at = excluded.updated_at",
+            params![
+                book_id.0,
+                position.chapter as i64,
+                position.sentence as i64,
+                position.word as i64,
+                now,
+            ],
+        )?;
+        conn.execute(
+            "INSERT INTO chapter_progress (book_id, chapter, sentence, word, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(book_id, chapter) DO UPDATE SET
+                sentence = excluded.sentence,
+                word = excluded.word,
+                updated_at = excluded.updated_at",
+            params![
+                book_id.0,
+                position.chapter as i64,
+                position.sentence as i64,
+                position.word as i64,
+                now,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Last position within one specific chapter, for non-linear reading.
+    /// The per-book position from `load_progress` stays the resume target.
+    pub fn load_chapter_progress(
+        &self,
+        book_id: &EbookId,
+        chapter: usize,
+    ) -> Result<Option<ReaderPosition>, PersistenceError> {
+        let conn = self.conn.lock();
+        let position = conn
+            .query_row(
+                "SELECT sentence, word FROM chapter_progress
+                 WHERE book_id = ?1 AND chapter = ?2",
+                params![book_id.0, chapter as i64],
+                |row| {
+                    Ok(ReaderPosition {
+                        chapter,
+                        sentence: row.get::<_, i64>(0)? as usize,
+                        word: row.get::<_, i64>(1)? as usize,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(position)
+    }
+
+    pub fn load_progress(
+        &self,
+        book_id: &EbookId,
+    ) -> Result<Option<ReaderPosition>, PersistenceError> {
+        let conn = self.conn.lock();
+        let position = conn
+            .query_row(
+                "SELECT chapter, sentence, word FROM reader_progress WHERE book_id = ?1",
+                params![book_id.0],
+                |row| {
+                    Ok(ReaderPosition {
+                        chapter: row.get::<_, i64>(0)? as usize,
+                        sentence: row.get::<_, i64>(1)? as usize,
+                        word: row.get::<_, i64>(2)? as usize,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(position)
+    }
+}
+
+impl Database {
+    /// Create a bookmark, returning its id. The note is optional and can
+    /// be edited later through `update_bookmark_note`.
+    pub fn add_bookmark(
+        &self,
+        book_id: &EbookId,
+        chapter: usize,
+        sentence: usize,
+        note: Option<&str>,
+    ) -> Result<i64, PersistenceError> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO bookmarks (book_id, chapter, sentence, note, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![book_id.0, chapter as i64, sentence as i64, note, unix_now()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// All bookmarks for a book, sorted by position within it.
+    pub fn bookmarks(&self, book_id: &EbookId) -> Result<Vec<Bookmark>, PersistenceError> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, chapter, sentence, note FROM bookmarks
+             WHERE book_id = ?1 ORDER BY chapter, sentence",
+        )?;
+        let rows = stmt.query_map(params![book_id.0], |row| {
+            Ok(Bookmark {
+                id: row.get(0)?,
+                book_id: book_id.clone(),
+                chapter: row.get::<_, i64>(1)? as usize,
+                sentence: row.get::<_, i64>(2)? as usize,
+                note: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Bookmarks within one chapter, for rendering inline markers in the
+    /// sentence list. One indexed query per chapter switch, so chapters
+    /// with many bookmarks stay cheap.
+    pub fn bookmarks_in_chapter(
+        &self,
+        book_id: &EbookId,
+        chapter: usize,
+    ) -> Result<Vec<Bookmark>, PersistenceError> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, sentence, note FROM bookmarks
+             WHERE book_id = ?1 AND chapter = ?2 ORDER BY sentence",
+        )?;
+        let rows = stmt.query_map(params![book_id.0, chapter as i64], |row| {
+            Ok(Bookmark {
+                id: row.get(0)?,
+                book_id: book_id.clone(),
+                chapter,
+                sentence: row.get::<_, i64>(1)? as usize,
+                note: row.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Record a highlighted passage, returning its id.
+    pub fn add_highlight(
+        &self,
+        book_id: &EbookId,
+        chapter: usize,
+        sentence: usize,
+        text: &str,
+        note: Option<&str>,
+        color: HighlightColor,
+    ) -> Result<i64, PersistenceError> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO highlights (book_id, chapter, sentence, text, note, color, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                book_id.0,
+                chapter as i64,
+                sentence as i64,
+                text,
+                note,
+                color.as_str(),
+                unix_now()
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// All highlights for a book, sorted by position within it. Passing
+    /// a color filters the list for the color-keyed highlights view.
+    pub fn highlights(&self, book_id: &EbookId) -> Result<Vec<Highlight>, PersistenceError> {
+        self.highlights_filtered(book_id, None)
+    }
+
+    pub fn highlights_filtered(
+        &self,
+        book_id: &EbookId,
+        color: Option<HighlightColor>,
+    ) -> Result<Vec<Highlight>, PersistenceError> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, chapter, sentence, text, note, color FROM highlights
+             WHERE book_id = ?1 AND (?2 IS NULL OR color = ?2)
+             ORDER BY chapter, sentence",
+        )?;
+        let rows = stmt.query_map(
+            params![book_id.0, color.map(|c| c.as_str())],
+            |row| {
+                Ok(Highlight {
+                    id: row.get(0)?,
+                    book_id: book_id.clone(),
+                    chapter: row.get::<_, i64>(1)? as usize,
+                    sentence: row.get::<_, i64>(2)? as usize,
+                    text: row.get(3)?,
+                    note: row.get(4)?,
+                    color: HighlightColor::from_stored(&row.get::<_, String>(5)?),
+                })
+            },
+        )?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn remove_highlight(&self, id: i64) -> Result<bool, PersistenceError> {
+        let changed = self
+            .conn
+            .lock()
+            .execute("DELETE FROM highlights WHERE id = ?1", params![id])?;
+        Ok(changed > 0)
+    }
+
+    /// Replace a bookmark's note; `None` clears it. Returns whether the
+    /// bookmark existed.
+    pub fn update_bookmark_note(
+        &self,
+        id: i64,
+        note: Option<&str>,
+    ) -> Result<bool, PersistenceError> {
+        let changed = self.conn.lock().execute(
+            "UPDATE bookmarks SET note = ?2 WHERE id = ?1",
+            params![id, note],
+        )?;
+        Ok(changed > 0)
+    }
+
+    /// Ids of the most recently opened books, newest first, ordered by
+    /// when their position was last written.
+    pub fn recent_books(&self, limit: usize) -> Result<Vec<EbookId>, PersistenceError> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT book_id FROM reader_progress
+             ORDER BY updated_at DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| Ok(EbookId(row.get(0)?)))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// `recent_books` joined against the live library, dropping ids whose
+    /// books no longer exist on disk. Asks for extra rows so stale entries
+    /// don't shrink the result below `limit`.
+    pub fn recent_books_in(
+        &self,
+        library: &crate::library::Library,
+        limit: usize,
+    ) -> Result<Vec<crate::library::Ebook>, PersistenceError> {
+        let ids = self.recent_books(limit.saturating_mul(2).max(limit + 8))?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| library.get(id))
+            .take(limit)
+            .collect())
+    }
+
+    /// Drop a book's saved position so it starts over from chapter 0,
+    /// optionally clearing its bookmarks too. Returns whether a position
+    /// row existed. Callers are expected to confirm with the user first.
+    pub fn clear_progress(
+        &self,
+        book_id: &EbookId,
+        clear_bookmarks: bool,
+    ) -> Result<bool, PersistenceError> {
+        let conn = self.conn.lock();
+        let changed = conn.execute(
+            "DELETE FROM reader_progress WHERE book_id = ?1",
+            params![book_id.0],
+        )?;
+        conn.execute(
+            "DELETE FROM chapter_progress WHERE book_id = ?1",
+            params![book_id.0],
+        )?;
+        if clear_bookmarks {
+            conn.execute(
+                "DELETE FROM bookmarks WHERE book_id = ?1",
+                params![book_id.0],
+            )?;
+        }
+        Ok(changed > 0)
+    }
+
+    /// Drop every saved position, returning how many were removed.
+    /// Bookmarks are left alone.
+    pub fn clear_all_progress(&self) -> Result<usize, PersistenceError> {
+        let conn = self.conn.lock();
+        let changed = conn.execute("DELETE FROM reader_progress", [])?;
+        conn.execute("DELETE FROM chapter_progress", [])?;
+        Ok(changed)
+    }
+
+    /// Upsert one app setting. Values are stored as strings; callers own
+    /// the encoding and validation of what they put in.
+    pub fn set_setting(&self, key: &str, value: &str) -> Result<(), PersistenceError> {
+        self.conn.lock().execute(
+            "INSERT INTO settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Remove one app setting. A no-op when the key was never set, so
+    /// callers can clear optional values unconditionally.
+    pub fn delete_setting(&self, key: &str) -> Result<(), PersistenceError> {
+        self.conn
+            .lock()
+            .execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+
+    pub fn get_setting(&self, key: &str) -> Result<Option<String>, PersistenceError> {
+        let conn = self.conn.lock();
+        let value = conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                params![key],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    /// Store a chapter's word-level sync map, keyed by the audio file's
+    /// mtime so a re-ripped or replaced file invalidates the alignment.
+    pub fn save_sync_map(
+        &self,
+        book_id: &EbookId,
+        chapter: usize,
+        audio_mtime: i64,
+        map: &crate::audio::SyncMap,
+    ) -> Result<(), PersistenceError> {
+        let points = serde_json::to_string(map.points()).map_err(PersistenceError::from)?;
+        self.conn.lock().execute(
+            "INSERT INTO sync_maps (book_id, chapter, audio_mtime, points)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(book_id, chapter) DO UPDATE SET
+                audio_mtime = excluded.audio_mtime,
+                points = excluded.points",
+            params![book_id.0, chapter as i64, audio_mtime, points],
+        )?;
+        Ok(())
+    }
+
+    /// Load a chapter's sync map, returning `None` when absent or when
+    /// the stored audio mtime no longer matches (stale alignment).
+    pub fn load_sync_map(
+        &self,
+        book_id: &EbookId,
+        chapter: usize,
+        audio_mtime: i64,
+    ) -> Result<Option<crate::audio::SyncMap>, PersistenceError> {
+        let conn = self.conn.lock();
+        let row = conn
+            .query_row(
+                "SELECT audio_mtime, points FROM sync_maps
+                 WHERE book_id = ?1 AND chapter = ?2",
+                params![book_id.0, chapter as i64],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        let Some((stored_mtime, points)) = row else {
+            return Ok(None);
+        };
+        if stored_mtime != audio_mtime {
+            return Ok(None);
+        }
+        let points: Vec<crate::audio::SyncPoint> =
+            serde_json::from_str(&points).map_err(PersistenceError::from)?;
+        let mut map = crate::audio::SyncMap::default();
+        for point in points {
+            map.push_point(point.text_index, point.timestamp);
+        }
+        Ok(Some(map))
+    }
+
+    /// Dump reader progress and reading sessions as CSV files
+    /// (`progress.csv`, `sessions.csv`) under `dir`, joining book titles
+    /// from the library snapshot so the export reads well in a
+    /// spreadsheet. Returns the paths written.
+    pub fn export_csv(
+        &self,
+        library: &crate::library::Library,
+        dir: &Path,
+    ) -> Result<Vec<PathBuf>, PersistenceError> {
+        std::fs::create_dir_all(dir)?;
+        let title_of = |id: &str| {
+            library
+                .get(&EbookId(id.to_string()))
+                .map(|book| book.title)
+                .unwrap_or_default()
+        };
+
+        let progress_path = dir.join("progress.csv");
+        {
+            let conn = self.conn.lock();
+            let mut stmt = conn.prepare(
+                "SELECT book_id, chapter, sentence, word, updated_at
+                 FROM reader_progress ORDER BY updated_at DESC",
+            )?;
+            let mut out = String::from("book_id,title,chapter,sentence,word,updated_at\n");
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            })?;
+            for row in rows {
+                let (book_id, chapter, sentence, word, updated_at) = row?;
+                out.push_str(&format!(
+                    "{},{},{chapter},{sentence},{word},{updated_at}\n",
+                    csv_field(&book_id),
+                    csv_field(&title_of(&book_id)),
+                ));
+            }
+            std::fs::write(&progress_path, out)?;
+        }
+
+        let sessions_path = dir.join("sessions.csv");
+        {
+            let conn = self.conn.lock();
+            let mut stmt = conn.prepare(
+                "SELECT book_id, started_at, ended_at FROM reading_sessions
+                 ORDER BY started_at DESC",
+            )?;
+            let mut out = String::from("book_id,title,started_at,ended_at,seconds\n");
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, Option<i64>>(2)?,
+                ))
+            })?;
+            for row in rows {
+                let (book_id, started_at, ended_at) = row?;
+                let seconds = ended_at.map(|end| end - started_at);
+                out.push_str(&format!(
+                    "{},{},{started_at},{},{}\n",
+                    csv_field(&book_id),
+                    csv_field(&title_of(&book_id)),
+                    ended_at.map(|v| v.to_string()).unwrap_or_default(),
+                    seconds.map(|v| v.to_string()).unwrap_or_default(),
+                ));
+            }
+            std::fs::write(&sessions_path, out)?;
+        }
+
+        Ok(vec![progress_path, sessions_path])
+    }
+
+    /// Render one book's highlights and annotated bookmarks as a
+    /// Markdown document, grouped by chapter with the highlighted text
+    /// quoted and the note below it. Returns `None` when the book has
+    /// nothing to export.
+    pub fn annotations_markdown(
+        &self,
+        book: &crate::library::Ebook,
+    ) -> Result<Option<String>, PersistenceError> {
+        let highlights = self.highlights(&book.id)?;
+        let bookmarks: Vec<Bookmark> = self
+            .bookmarks(&book.id)?
+            .into_iter()
+            .filter(|mark| mark.note.is_some())
+            .collect();
+        if highlights.is_empty() && bookmarks.is_empty() {
+            return Ok(None);
+        }
+
+        let mut out = format!("# {}\n", book.title);
+        if let Some(author) = &book.author {
+            out.push_str(&format!("by {author}\n"));
+        }
+        let mut chapters: Vec<usize> = highlights
+            .iter()
+            .map(|h| h.chapter)
+            .chain(bookmarks.iter().map(|m| m.chapter))
+            .collect();
+        chapters.sort_unstable();
+        chapters.dedup();
+        for chapter in chapters {
+            out.push_str(&format!("\n## {}\n", chapter_heading(book, chapter)));
+            for highlight in highlights.iter().filter(|h| h.chapter == chapter) {
+                out.push_str(&format!("\n> {}\n", highlight.text));
+                match &highlight.note {
+                    Some(note) => {
+                        out.push_str(&format!("\n*{}*: {note}\n", highlight.color.as_str()))
+                    }
+                    None => out.push_str(&format!("\n*{}*\n", highlight.color.as_str())),
+                }
+            }
+            for mark in bookmarks.iter().filter(|m| m.chapter == chapter) {
+                let note = mark.note.as_deref().unwrap_or_default();
+                out.push_str(&format!(
+                    "\n- Bookmark at sentence {}: {note}\n",
+                    mark.sentence + 1
+                ));
+            }
+        }
+        Ok(Some(out))
+    }
+
+    /// "Export all": write one Markdown file per annotated book under
+    /// `dir`, skipping books with nothing to export. Returns the paths
+    /// written.
+    pub fn export_annotations_markdown(
+        &self,
+        library: &crate::library::Library,
+        dir: &Path,
+    ) -> Result<Vec<PathBuf>, PersistenceError> {
+        std::fs::create_dir_all(dir)?;
+        let mut written = Vec::new();
+        for book in library.iter() {
+            let Some(markdown) = self.annotations_markdown(&book)? else {
+                continue;
+            };
+            let path = dir.join(format!("{}.md", filename_stem(&book.title)));
+            std::fs::write(&path, markdown)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Record a reader-window open; a single insert, cheap enough to run
+    /// inline while the window comes up.
+    pub fn start_reading_session(&self, book_id: &EbookId) -> Result<i64, PersistenceError> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO reading_sessions (book_id, started_at) VALUES (?1, ?2)",
+            params![book_id.0, unix_now()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Stamp the session's end time on close.
+    pub fn end_reading_session(&self, session_id: i64) -> Result<(), PersistenceError> {
+        self.conn.lock().execute(
+            "UPDATE reading_sessions SET ended_at = ?2 WHERE id = ?1",
+            params![session_id, unix_now()],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent sessions, newest first.
+    pub fn recent_sessions(&self, limit: usize) -> Result<Vec<ReadingSession>, PersistenceError> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, book_id, started_at, ended_at FROM reading_sessions
+             ORDER BY started_at DESC, id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            Ok(ReadingSession {
+                id: row.get(0)?,
+                book_id: EbookId(row.get(1)?),
+                started_at: row.get(2)?,
+                ended_at: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Total completed reading time per calendar day (UTC), newest first,
+    /// as `(YYYY-MM-DD, seconds)` pairs. Open-ended sessions are skipped.
+    pub fn reading_time_per_day(&self) -> Result<Vec<(String, i64)>, PersistenceError> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT date(started_at, 'unixepoch') AS day,
+                    SUM(ended_at - started_at)
+             FROM reading_sessions
+             WHERE ended_at IS NOT NULL
+             GROUP BY day ORDER BY day DESC",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Remember which engine and voice narrated a book. Called whenever
+    /// the user changes either while the book is open.
+    pub fn save_book_voice(
+        &self,
+        book_id: &EbookId,
+        preference: &VoicePreference,
+    ) -> Result<(), PersistenceError> {
+        self.conn.lock().execute(
+            "INSERT INTO book_voices (book_id, engine, voice, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(book_id) DO UPDATE SET
+                engine = excluded.engine,
+                voice = excluded.voice,
+                updated_at = excluded.updated_at",
+            params![
+                book_id.0,
+                preference.engine,
+                preference.voice,
+                unix_now()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The stored narration preference for a book, if any.
+    pub fn load_book_voice(
+        &self,
+        book_id: &EbookId,
+    ) -> Result<Option<VoicePreference>, PersistenceError> {
+        Ok(self
+            .conn
+            .lock()
+            .query_row(
+                "SELECT engine, voice FROM book_voices WHERE book_id = ?1",
+                params![book_id.0],
+                |row| {
+                    Ok(VoicePreference {
+                        engine: row.get(0)?,
+                        voice: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?)
+    }
+
+    /// The stored preference, but only when its engine still exists —
+    /// `is_registered` is the caller's view of the currently available
+    /// engines. `None` means fall back to the environment defaults, the
+    /// same as a book that was never narrated.
+    pub fn resolve_book_voice(
+        &self,
+        book_id: &EbookId,
+        is_registered: impl Fn(&str) -> bool,
+    ) -> Result<Option<VoicePreference>, PersistenceError> {
+        Ok(self
+            .load_book_voice(book_id)?
+            .filter(|preference| is_registered(&preference.engine)))
+    }
+
+    pub fn remove_bookmark(&self, id: i64) -> Result<bool, PersistenceError> {
+        let changed = self
+            .conn
+            .lock()
+            .execute("DELETE FROM bookmarks WHERE id = ?1", params![id])?;
+        Ok(changed > 0)
+    }
+}
+
+/// UI theme choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Library list ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Title,
+    Author,
+    RecentlyAdded,
+}
+
+/// The global application settings, loaded once at startup from the
+/// `settings` table and written back field-by-field as the user changes
+/// them. Every feature that needs a persisted preference goes through
+/// this struct instead of inventing its own storage; missing or
+/// unparseable rows fall back to the defaults.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub theme: Theme,
+    pub font_family: Option<String>,
+    pub font_size: u32,
+    /// Whether the text view follows the spoken word.
+    pub follow_mode: bool,
+    pub default_engine: Option<String>,
+    pub default_voice: Option<String>,
+    pub default_rate: f32,
+    pub sort_order: SortOrder,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::System,
+            font_family: None,
+            font_size: 16,
+            follow_mode: true,
+            default_engine: None,
+            default_voice: None,
+            default_rate: 1.0,
+            sort_order: SortOrder::Title,
+        }
+    }
+}
+
+impl Settings {
+    pub const FONT_SIZE_RANGE: (u32, u32) = (8, 48);
+    pub const RATE_RANGE: (f32, f32) = (0.5, 3.0);
+
+    const KEY_THEME: &'static str = "ui.theme";
+    const KEY_FONT_FAMILY: &'static str = "ui.font_family";
+    const KEY_FONT_SIZE: &'static str = "ui.font_size";
+    const KEY_FOLLOW_MODE: &'static str = "reader.follow_mode";
+    const KEY_DEFAULT_ENGINE: &'static str = "tts.default_engine";
+    const KEY_DEFAULT_VOICE: &'static str = "tts.default_voice";
+    const KEY_DEFAULT_RATE: &'static str = "tts.default_rate";
+    const KEY_SORT_ORDER: &'static str = "library.sort_order";
+
+    /// Load the settings, substituting defaults for anything missing.
+    /// Out-of-range numbers are clamped like the timing settings, so a
+    /// corrupt row can't produce an unreadable font or a frozen rate.
+    pub fn load(db: &Database) -> Self {
+        let base = Self::default();
+        let raw = |key: &str| db.get_setting(key).ok().flatten();
+        Self {
+            theme: raw(Self::KEY_THEME)
+                .map(|value| match value.as_str() {
+                    "light" => Theme::Light,
+                    "dark" => Theme::Dark,
+                    _ => Theme::System,
+                })
+                .unwrap_or(base.theme),
+            font_family: raw(Self::KEY_FONT_FAMILY),
+            font_size: raw(Self::KEY_FONT_SIZE)
+                .and_then(|value| value.parse::<u32>().ok())
+                .map(|size| size.clamp(Self::FONT_SIZE_RANGE.0, Self::FONT_SIZE_RANGE.1))
+                .unwrap_or(base.font_size),
+            follow_mode: raw(Self::KEY_FOLLOW_MODE)
+                .map(|value| value == "true")
+                .unwrap_or(base.follow_mode),
+            default_engine: raw(Self::KEY_DEFAULT_ENGINE),
+            default_voice: raw(Self::KEY_DEFAULT_VOICE),
+            default_rate: raw(Self::KEY_DEFAULT_RATE)
+                .and_then(|value| value.parse::<f32>().ok())
+                .map(|rate| rate.clamp(Self::RATE_RANGE.0, Self::RATE_RANGE.1))
+                .unwrap_or(base.default_rate),
+            sort_order: raw(Self::KEY_SORT_ORDER)
+                .map(|value| match value.as_str() {
+                    "author" => SortOrder::Author,
+                    "recently_added" => SortOrder::RecentlyAdded,
+                    _ => SortOrder::Title,
+                })
+                .unwrap_or(base.sort_order),
+        }
+    }
+
+    /// Write every field back. Individual setters aren't needed: the
+    /// struct is small and settings change at human speed. Optional
+    /// fields set to `None` delete their row, so a cleared value stays
+    /// cleared on reload instead of resurrecting from the table.
+    pub fn persist(&self, db: &Database) -> Result<(), PersistenceError> {
+        let set_or_delete = |key: &str, value: &Option<String>| match value {
+            Some(value) => db.set_setting(key, value),
+            None => db.delete_setting(key),
+        };
+        db.set_setting(
+            Self::KEY_THEME,
+            match self.theme {
+                Theme::System => "system",
+                Theme::Light => "light",
+                Theme::Dark => "dark",
+            },
+        )?;
+        set_or_delete(Self::KEY_FONT_FAMILY, &self.font_family)?;
+        db.set_setting(Self::KEY_FONT_SIZE, &self.font_size.to_string())?;
+        db.set_setting(
+            Self::KEY_FOLLOW_MODE,
+            if self.follow_mode { "true" } else { "false" },
+        )?;
+        set_or_delete(Self::KEY_DEFAULT_ENGINE, &self.default_engine)?;
+        set_or_delete(Self::KEY_DEFAULT_VOICE, &self.default_voice)?;
+        db.set_setting(Self::KEY_DEFAULT_RATE, &self.default_rate.to_string())?;
+        db.set_setting(
+            Self::KEY_SORT_ORDER,
+            match self.sort_order {
+                SortOrder::Title => "title",
+                SortOrder::Author => "author",
+                SortOrder::RecentlyAdded => "recently_added",
+            },
+        )
+    }
+}
+
+/// Resolved database location. `VANILLA_READER_DB_PATH` takes precedence
+/// (mirroring `VANILLA_READER_LIBRARY_ROOT`) so the database can live in
+/// a synced folder or a portable install; otherwise the default is
+/// `data_local_dir()/vanilla-ebook-reader/progress.sqlite`. Parent
+/// directories are created on open either way.
+pub fn db_path() -> Result<PathBuf, PersistenceError> {
+    if let Some(path) = std::env::var_os("VANILLA_READER_DB_PATH") {
+        if !path.is_empty() {
+            return Ok(PathBuf::from(path));
+        }
+    }
+    let base = dirs::data_local_dir().ok_or(PersistenceError::NoDataDir)?;
+    Ok(base.join("vanilla-ebook-reader").join("progress.sqlite"))
+}
+
+/// Heading for a chapter in the Markdown export: the audio chapter's
+/// title when the book has one, a plain "Chapter N" otherwise.
+fn chapter_heading(book: &crate::library::Ebook, chapter: usize) -> String {
+    book.audio_chapters
+        .iter()
+        .find(|c| c.chapter_index == chapter)
+        .map(|c| c.title.clone())
+        .unwrap_or_else(|| format!("Chapter {}", chapter + 1))
+}
+
+/// A book title reduced to something safe as a filename.
+fn filename_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' { c } else { '_' })
+        .collect();
+    let stem = stem.trim().to_string();
+    if stem.is_empty() {
+        "untitled".to_string()
+    } else {
+        stem
+    }
+}
+
+/// Quote a CSV field when it contains separators, quotes, or newlines.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) fn temp_db(tag: &str) -> (PathBuf, Database) {
+        let path = std::env::temp_dir()
+            .join(format!("rust_core_db_{tag}_{}", std::process::id()))
+            .join("progress.sqlite");
+        let _ = std::fs::remove_file(&path);
+        let db = Database::open_at(&path).unwrap();
+        (path, db)
+    }
+
+    #[test]
+    fn bookmarks_sort_by_position_and_notes_are_editable() {
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("book".into());
+        let late = db.add_bookmark(&id, 4, 2, None).unwrap();
+        let early = db.add_bookmark(&id, 1, 7, Some("great quote")).unwrap();
+
+        let marks = db.bookmarks(&id).unwrap();
+        assert_eq!(marks.len(), 2);
+        assert_eq!(marks[0].id, early);
+        assert_eq!(marks[0].note.as_deref(), Some("great quote"));
+        assert_eq!(marks[1].id, late);
+
+        assert!(db.update_bookmark_note(late, Some("look this up")).unwrap());
+        assert!(!db.update_bookmark_note(9999, None).unwrap());
+        assert!(db.remove_bookmark(early).unwrap());
+        assert_eq!(db.bookmarks(&id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn chapter_bookmarks_filter_and_sort_by_sentence() {
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("book".into());
+        db.add_bookmark(&id, 2, 30, None).unwrap();
+        db.add_bookmark(&id, 2, 5, Some("here")).unwrap();
+        db.add_bookmark(&id, 3, 1, None).unwrap();
+
+        let marks = db.bookmarks_in_chapter(&id, 2).unwrap();
+        assert_eq!(
+            marks.iter().map(|m| m.sentence).collect::<Vec<_>>(),
+            vec![5, 30]
+        );
+        assert!(db.bookmarks_in_chapter(&id, 9).unwrap().is_empty());
+    }
+
+    #[test]
+    fn annotations_export_groups_by_chapter_with_header() {
+        let db = Database::open_in_memory().unwrap();
+        let book = crate::library::Ebook {
+            id: EbookId("moby".into()),
+            title: "Moby-Dick".into(),
+            author: Some("Herman Melville".into()),
+            description: None,
+            path: PathBuf::from("moby"),
+            audio_chapters: Vec::new(),
+            text: None,
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        };
+        assert_eq!(db.annotations_markdown(&book).unwrap(), None);
+
+        db.add_highlight(
+            &book.id,
+            1,
+            4,
+            "Call me Ishmael.",
+            Some("famous opener"),
+            HighlightColor::Green,
+        )
+        .unwrap();
+        db.add_highlight(&book.id, 0, 2, "Loomings.", None, HighlightColor::default())
+            .unwrap();
+        db.add_bookmark(&book.id, 1, 9, Some("reread this")).unwrap();
+        db.add_bookmark(&book.id, 2, 0, None).unwrap();
+
+        let markdown = db.annotations_markdown(&book).unwrap().unwrap();
+        assert!(markdown.starts_with("# Moby-Dick\nby Herman Melville\n"));
+        assert!(markdown.contains("## Chapter 1\n\n> Loomings.\n\n*yellow*"));
+        assert!(markdown.contains("> Call me Ishmael.\n\n*green*: famous opener"));
+        assert!(markdown.contains("- Bookmark at sentence 10: reread this"));
+        // The note-less bookmark's chapter isn't exported at all.
+        assert!(!markdown.contains("Chapter 3"));
+
+        let library = crate::library::Library::new();
+        library.replace_all(vec![book]);
+        let dir = std::env::temp_dir().join(format!(
+            "rust_core_annotations_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let written = db.export_annotations_markdown(&library, &dir).unwrap();
+        assert_eq!(written.len(), 1);
+        assert!(written[0].ends_with("Moby-Dick.md"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn highlights_filter_by_color_and_tolerate_unknown_stored_colors() {
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("book".into());
+        db.add_highlight(&id, 0, 0, "a", None, HighlightColor::Yellow)
+            .unwrap();
+        db.add_highlight(&id, 0, 1, "b", None, HighlightColor::Blue)
+            .unwrap();
+        db.add_highlight(&id, 1, 0, "c", None, HighlightColor::Blue)
+            .unwrap();
+
+        assert_eq!(db.highlights(&id).unwrap().len(), 3);
+        let blue = db
+            .highlights_filtered(&id, Some(HighlightColor::Blue))
+            .unwrap();
+        assert_eq!(blue.len(), 2);
+        assert!(blue.iter().all(|h| h.color == HighlightColor::Blue));
+
+        // A row written by a future version with a color we don't know
+        // reads back as yellow instead of failing the whole list.
+        db.conn
+            .lock()
+            .execute(
+                "UPDATE highlights SET color = 'chartreuse' WHERE text = 'a'",
+                [],
+            )
+            .unwrap();
+        assert_eq!(db.highlights(&id).unwrap()[0].color, HighlightColor::Yellow);
+    }
+
+    #[test]
+    fn sync_maps_round_trip_and_invalidate_on_mtime_change() {
+        use crate::audio::SyncMap;
+        use std::time::Duration;
+
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("book".into());
+        let mut map = SyncMap::default();
+        map.push_point(0, Duration::from_millis(0));
+        map.push_point(12, Duration::from_millis(800));
+
+        db.save_sync_map(&id, 3, 1_000, &map).unwrap();
+        let loaded = db.load_sync_map(&id, 3, 1_000).unwrap().unwrap();
+        assert_eq!(loaded.resolve_index(Duration::from_millis(900)), Some(12));
+
+        // A changed audio file invalidates the stored alignment.
+        assert_eq!(db.load_sync_map(&id, 3, 2_000).unwrap(), None);
+        assert_eq!(db.load_sync_map(&id, 4, 1_000).unwrap(), None);
+    }
+
+    #[test]
+    fn settings_default_round_trip_and_clamp() {
+        let db = Database::open_in_memory().unwrap();
+        assert_eq!(Settings::load(&db), Settings::default());
+
+        let settings = Settings {
+            theme: Theme::Dark,
+            font_family: Some("Literata".into()),
+            font_size: 20,
+            follow_mode: false,
+            default_engine: Some("models/en_US-amy.onnx".into()),
+            default_voice: Some("amy".into()),
+            default_rate: 1.4,
+            sort_order: SortOrder::RecentlyAdded,
+        };
+        settings.persist(&db).unwrap();
+        assert_eq!(Settings::load(&db), settings);
+
+        // Garbage rows fall back or clamp instead of breaking startup.
+        db.set_setting("ui.font_size", "9999").unwrap();
+        db.set_setting("ui.theme", "solarized").unwrap();
+        db.set_setting("tts.default_rate", "not a number").unwrap();
+        let loaded = Settings::load(&db);
+        assert_eq!(loaded.font_size, Settings::FONT_SIZE_RANGE.1);
+        assert_eq!(loaded.theme, Theme::System);
+        assert_eq!(loaded.default_rate, Settings::default().default_rate);
+    }
+
+    #[test]
+    fn cleared_optional_settings_stay_cleared_after_reload() {
+        let db = Database::open_in_memory().unwrap();
+        let mut settings = Settings {
+            font_family: Some("Literata".into()),
+            default_engine: Some("models/en_US-amy.onnx".into()),
+            default_voice: Some("amy".into()),
+            ..Settings::default()
+        };
+        settings.persist(&db).unwrap();
+        assert_eq!(Settings::load(&db), settings);
+
+        // Clearing back to None must delete the rows, not leave the old
+        // values behind to resurrect on the next load.
+        settings.font_family = None;
+        settings.default_engine = None;
+        settings.default_voice = None;
+        settings.persist(&db).unwrap();
+
+        let reloaded = Settings::load(&db);
+        assert_eq!(reloaded.font_family, None);
+        assert_eq!(reloaded.default_engine, None);
+        assert_eq!(reloaded.default_voice, None);
+        assert_eq!(db.get_setting(Settings::KEY_FONT_FAMILY).unwrap(), None);
+    }
+
+    #[test]
+    fn book_voices_round_trip_and_drop_unregistered_engines() {
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("book".into());
+        assert_eq!(db.load_book_voice(&id).unwrap(), None);
+
+        let preference = VoicePreference {
+            engine: "models/en_US-amy.onnx".into(),
+            voice: Some("amy".into()),
+        };
+        db.save_book_voice(&id, &preference).unwrap();
+        assert_eq!(db.load_book_voice(&id).unwrap(), Some(preference.clone()));
+
+        // Changing the voice overwrites rather than duplicating.
+        let changed = VoicePreference {
+            engine: "models/en_GB-alan.onnx".into(),
+            voice: None,
+        };
+        db.save_book_voice(&id, &changed).unwrap();
+        assert_eq!(db.load_book_voice(&id).unwrap(), Some(changed.clone()));
+
+        assert_eq!(
+            db.resolve_book_voice(&id, |engine| engine == changed.engine)
+                .unwrap(),
+            Some(changed)
+        );
+        // The model was removed: fall back to environment defaults.
+        assert_eq!(db.resolve_book_voice(&id, |_| false).unwrap(), None);
+    }
+
+    #[test]
+    fn recovers_from_a_corrupt_database_file() {
+        let dir = std::env::temp_dir().join(format!("rust_core_corrupt_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("progress.sqlite");
+        std::fs::write(&path, b"definitely not a sqlite file").unwrap();
+
+        let db = Database::open_or_recover(&path).unwrap();
+        let id = EbookId("book".into());
+        db.save_progress(&id, ReaderPosition::default()).unwrap();
+        assert!(path.with_extension("sqlite.corrupt").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn db_path_prefers_environment_override() {
+        std::env::set_var("VANILLA_READER_DB_PATH", "/tmp/custom/progress.sqlite");
+        let path = db_path().unwrap();
+        std::env::remove_var("VANILLA_READER_DB_PATH");
+        assert_eq!(path, PathBuf::from("/tmp/custom/progress.sqlite"));
+
+        let fallback = db_path().unwrap();
+        assert!(fallback.ends_with("vanilla-ebook-reader/progress.sqlite"));
+    }
+
+    #[test]
+    fn csv_export_includes_titles_and_quotes_fields() {
+        use crate::library::{Ebook, Library};
+
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("book".into());
+        db.save_progress(&id, ReaderPosition { chapter: 1, sentence: 2, word: 3 })
+            .unwrap();
+        let session = db.start_reading_session(&id).unwrap();
+        db.end_reading_session(session).unwrap();
+
+        let library = Library::new();
+        library.insert(Ebook {
+            id: id.clone(),
+            title: "Hello, \"World\"".into(),
+            author: None,
+            description: None,
+            path: PathBuf::from("book"),
+            audio_chapters: Vec::new(),
+            text: None,
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        });
+
+        let dir = std::env::temp_dir().join(format!("rust_core_csv_{}", std::process::id()));
+        let written = db.export_csv(&library, &dir).unwrap();
+        assert_eq!(written.len(), 2);
+        let progress = std::fs::read_to_string(&written[0]).unwrap();
+        assert!(progress.starts_with("book_id,title,chapter"));
+        assert!(progress.contains("\"Hello, \"\"World\"\"\",1,2,3"));
+        let sessions = std::fs::read_to_string(&written[1]).unwrap();
+        assert_eq!(sessions.lines().count(), 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn clear_progress_resets_position_and_optionally_bookmarks() {
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("book".into());
+        db.save_progress(&id, ReaderPosition { chapter: 2, sentence: 5, word: 0 })
+            .unwrap();
+        db.add_bookmark(&id, 2, 5, None).unwrap();
+
+        assert!(db.clear_progress(&id, false).unwrap());
+        assert_eq!(db.load_progress(&id).unwrap(), None);
+        assert_eq!(db.bookmarks(&id).unwrap().len(), 1);
+
+        db.save_progress(&id, ReaderPosition::default()).unwrap();
+        assert!(db.clear_progress(&id, true).unwrap());
+        assert!(db.bookmarks(&id).unwrap().is_empty());
+        assert!(!db.clear_progress(&id, false).unwrap());
+
+        db.save_progress(&id, ReaderPosition::default()).unwrap();
+        assert_eq!(db.clear_all_progress().unwrap(), 1);
+    }
+
+    #[test]
+    fn recent_books_orders_by_last_update_and_drops_missing() {
+        use crate::library::{Ebook, Library};
+
+        let db = Database::open_in_memory().unwrap();
+        let gone = EbookId("gone".into());
+        let kept = EbookId("kept".into());
+        db.save_progress(&gone, ReaderPosition::default()).unwrap();
+        // Later write wins the ordering.
+        db.conn
+            .lock()
+            .execute(
+                "UPDATE reader_progress SET updated_at = updated_at - 100 WHERE book_id = 'gone'",
+                [],
+            )
+            .unwrap();
+        db.save_progress(&kept, ReaderPosition::default()).unwrap();
+
+        let ids = db.recent_books(10).unwrap();
+        assert_eq!(ids, vec![kept.clone(), gone.clone()]);
+
+        let library = Library::new();
+        library.insert(Ebook {
+            id: kept.clone(),
+            title: "Kept".into(),
+            author: None,
+            description: None,
+            path: PathBuf::from("kept"),
+            audio_chapters: Vec::new(),
+            text: None,
+            sync_files: Vec::new(),
+            series: None,
+            series_index: None,
+            added_at: None,
+        });
+        let books = db.recent_books_in(&library, 10).unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].id, kept);
+    }
+
+    #[test]
+    fn sessions_record_open_close_and_aggregate_per_day() {
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("book".into());
+        let session = db.start_reading_session(&id).unwrap();
+        let recent = db.recent_sessions(5).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].ended_at, None);
+
+        db.end_reading_session(session).unwrap();
+        let recent = db.recent_sessions(5).unwrap();
+        assert!(recent[0].ended_at.is_some());
+
+        let per_day = db.reading_time_per_day().unwrap();
+        assert_eq!(per_day.len(), 1);
+        assert!(per_day[0].1 >= 0);
+    }
+
+    #[test]
+    fn saves_and_reloads_positions() {
+        let (path, db) = temp_db("roundtrip");
+        let id = EbookId("some/book".into());
+        assert_eq!(db.load_progress(&id).unwrap(), None);
+
+        let position = ReaderPosition {
+            chapter: 3,
+            sentence: 12,
+            word: 4,
+        };
+        db.save_progress(&id, position).unwrap();
+        assert_eq!(db.load_progress(&id).unwrap(), Some(position));
+
+        // Upsert keeps one row per book.
+        db.save_progress(&id, ReaderPosition::default()).unwrap();
+        assert_eq!(
+            db.load_progress(&id).unwrap(),
+            Some(ReaderPosition::default())
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn chapter_positions_survive_reading_elsewhere() {
+        let db = Database::open_in_memory().unwrap();
+        let id = EbookId("some/book".into());
+
+        let in_chapter_2 = ReaderPosition {
+            chapter: 2,
+            sentence: 40,
+            word: 1,
+        };
+        db.save_progress(&id, in_chapter_2).unwrap();
+        // Jump to chapter 7 and read a bit.
+        let in_chapter_7 = ReaderPosition {
+            chapter: 7,
+            sentence: 3,
+            word: 0,
+        };
+        db.save_progress(&id, in_chapter_7).unwrap();
+
+        // Resume target is the most recent position...
+        assert_eq!(db.load_progress(&id).unwrap(), Some(in_chapter_7));
+        // ...but chapter 2 still remembers where we left off.
+        assert_eq!(
+            db.load_chapter_progress(&id, 2).unwrap(),
+            Some(in_chapter_2)
+        );
+        assert_eq!(db.load_chapter_progress(&id, 5).unwrap(), None);
+
+        db.clear_progress(&id, false).unwrap();
+        assert_eq!(db.load_chapter_progress(&id, 2).unwrap(), None);
+    }
+}
@@ -0,0 +1,143 @@
+//! Column detection for PDF text extraction.
+//!
+//! Extractors that walk the content stream interleave multi-column
+//! pages line by line, producing jumbled sentences. Given the extracted
+//! spans with their page coordinates, this pass clusters them into
+//! columns by x position so the page reads top-to-bottom within each
+//! column, left-to-right across columns.
+
+/// One extracted text span with its page-space position (PDF user
+/// units, y increasing downward after the extractor's flip).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionedText {
+    pub x: f32,
+    pub y: f32,
+    pub text: String,
+}
+
+/// Fraction of the page's x spread a gap must cover to count as a
+/// column gutter. Wide enough that ragged line endings don't split a
+/// single column in two.
+const MIN_GUTTER_FRACTION: f32 = 0.2;
+
+/// Fraction of spans each side of a gutter must hold; guards against
+/// "detecting" a column out of a stray margin note.
+const MIN_COLUMN_FRACTION: f32 = 0.2;
+
+/// Reassemble `spans` in column reading order. Returns `None` when the
+/// layout analysis is inconclusive (no convincing gutter), so callers
+/// fall back to the extractor's native order unchanged.
+pub fn reorder_columns(spans: &[PositionedText]) -> Option<String> {
+    let boundary = column_boundary(spans)?;
+    let mut left: Vec<&PositionedText> = Vec::new();
+    let mut right: Vec<&PositionedText> = Vec::new();
+    for span in spans {
+        if span.x < boundary {
+            left.push(span);
+        } else {
+            right.push(span);
+        }
+    }
+    let read = |column: &mut Vec<&PositionedText>| {
+        column.sort_by(|a, b| {
+            (a.y, a.x)
+                .partial_cmp(&(b.y, b.x))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        column
+            .iter()
+            .map(|span| span.text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    Some(format!("{}\n\n{}", read(&mut left), read(&mut right)))
+}
+
+/// Midpoint of the widest x gap, when that gap convincingly splits the
+/// spans into two populated columns.
+fn column_boundary(spans: &[PositionedText]) -> Option<f32> {
+    if spans.len() < 4 {
+        return None;
+    }
+    let mut xs: Vec<f32> = spans.iter().map(|span| span.x).collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let spread = xs.last()? - xs.first()?;
+    if spread <= 0.0 {
+        return None;
+    }
+    let (mut widest, mut boundary) = (0.0f32, None);
+    for pair in xs.windows(2) {
+        let gap = pair[1] - pair[0];
+        if gap > widest {
+            widest = gap;
+            boundary = Some((pair[0] + pair[1]) / 2.0);
+        }
+    }
+    let boundary = boundary?;
+    if widest / spread < MIN_GUTTER_FRACTION {
+        return None;
+    }
+    // The gutter must also dwarf the typical x jitter between lines,
+    // or a ragged narrow column would split on noise.
+    let mut gaps: Vec<f32> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    gaps.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median_gap = gaps[gaps.len() / 2];
+    if widest < 8.0 * median_gap {
+        return None;
+    }
+    let left = spans.iter().filter(|span| span.x < boundary).count();
+    let minimum = (spans.len() as f32 * MIN_COLUMN_FRACTION).ceil() as usize;
+    if left < minimum || spans.len() - left < minimum {
+        return None;
+    }
+    Some(boundary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(x: f32, y: f32, text: &str) -> PositionedText {
+        PositionedText {
+            x,
+            y,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn two_column_pages_read_down_then_across() {
+        // Extractor order interleaves the columns line by line.
+        let spans = vec![
+            span(50.0, 100.0, "Left one."),
+            span(320.0, 100.0, "Right one."),
+            span(50.0, 120.0, "Left two."),
+            span(320.0, 120.0, "Right two."),
+        ];
+        assert_eq!(
+            reorder_columns(&spans).as_deref(),
+            Some("Left one. Left two.\n\nRight one. Right two.")
+        );
+    }
+
+    #[test]
+    fn single_column_layout_is_inconclusive() {
+        let spans = vec![
+            span(50.0, 100.0, "One."),
+            span(54.0, 120.0, "Two."),
+            span(52.0, 140.0, "Three."),
+            span(51.0, 160.0, "Four."),
+        ];
+        assert_eq!(reorder_columns(&spans), None);
+    }
+
+    #[test]
+    fn a_stray_margin_note_does_not_make_a_column() {
+        let mut spans: Vec<PositionedText> = (0..8)
+            .map(|i| span(50.0 + i as f32, 100.0 + 10.0 * i as f32, "Body."))
+            .collect();
+        spans.push(span(500.0, 100.0, "Note."));
+        assert_eq!(reorder_columns(&spans), None);
+    }
+}
@@ -0,0 +1,120 @@
+//! Emphasis extraction for expressive TTS.
+//!
+//! Flattening HTML to plain text loses `<em>`/`<strong>` spans, so every
+//! engine reads flat. This pass unwraps the emphasis tags while recording
+//! where they were, keeping the plain body for display and letting
+//! SSML-capable engines receive `<emphasis>` markup instead.
+
+use std::ops::Range;
+
+use super::ruby::find_tag;
+
+/// Inline tags treated as emphasis. `<i>`/`<b>` are included because
+/// older EPUBs use them where `<em>`/`<strong>` is meant.
+const EMPHASIS_TAGS: &[&str] = &["em", "strong", "i", "b"];
+
+/// Plain text with the byte ranges that were emphasized in the source
+/// markup. The body is what the reader displays and non-SSML engines
+/// speak; the spans only matter for synthesis.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EmphasizedText {
+    pub body: String,
+    pub spans: Vec<Range<usize>>,
+}
+
+impl EmphasizedText {
+    /// The body as an SSML fragment with each recorded span wrapped in
+    /// `<emphasis>`, for engines that support it. Reserved characters
+    /// are escaped so stray `<` in the text can't break the document.
+    pub fn to_ssml(&self) -> String {
+        let mut out = String::from("<speak>");
+        let mut cursor = 0usize;
+        for span in &self.spans {
+            out.push_str(&escape_xml(&self.body[cursor..span.start]));
+            out.push_str("<emphasis level=\"moderate\">");
+            out.push_str(&escape_xml(&self.body[span.clone()]));
+            out.push_str("</emphasis>");
+            cursor = span.end;
+        }
+        out.push_str(&escape_xml(&self.body[cursor..]));
+        out.push_str("</speak>");
+        out
+    }
+}
+
+/// Unwrap emphasis tags from `html`, returning the text with spans for
+/// what they wrapped. Other markup passes through untouched; an
+/// unterminated tag is dropped and the rest emitted as-is.
+pub fn extract_emphasis(html: &str) -> EmphasizedText {
+    let mut body = String::with_capacity(html.len());
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    let mut rest = html;
+    loop {
+        let Some((tag, open)) = next_emphasis_tag(rest) else {
+            body.push_str(rest);
+            break;
+        };
+        body.push_str(&rest[..open.start]);
+        let after_open = &rest[open.end..];
+        let closing = format!("/{tag}");
+        let Some(close) = find_tag(after_open, &closing) else {
+            body.push_str(after_open);
+            break;
+        };
+        let start = body.len();
+        body.push_str(&after_open[..close.start]);
+        // Adjacent or nested emphasis merges into one span so the SSML
+        // output never emits overlapping elements.
+        match spans.last_mut() {
+            Some(last) if last.end >= start => last.end = body.len(),
+            _ => spans.push(start..body.len()),
+        }
+        rest = &after_open[close.end..];
+    }
+    EmphasizedText { body, spans }
+}
+
+/// Earliest emphasis tag in `html`, with its name and byte range.
+fn next_emphasis_tag(html: &str) -> Option<(&'static str, Range<usize>)> {
+    EMPHASIS_TAGS
+        .iter()
+        .filter_map(|tag| find_tag(html, tag).map(|range| (*tag, range)))
+        .min_by_key(|(_, range)| range.start)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emphasis_tags_unwrap_into_spans() {
+        let html = "A <em>very</em> important <strong>point</strong>.";
+        let extracted = extract_emphasis(html);
+        assert_eq!(extracted.body, "A very important point.");
+        assert_eq!(extracted.spans.len(), 2);
+        assert_eq!(&extracted.body[extracted.spans[0].clone()], "very");
+        assert_eq!(&extracted.body[extracted.spans[1].clone()], "point");
+    }
+
+    #[test]
+    fn ssml_wraps_spans_and_escapes_reserved_characters() {
+        let extracted = extract_emphasis("Tom <i>&amp; Jerry</i>");
+        assert_eq!(
+            extracted.to_ssml(),
+            "<speak>Tom <emphasis level=\"moderate\">&amp;amp; Jerry</emphasis></speak>"
+        );
+    }
+
+    #[test]
+    fn plain_text_round_trips_without_spans() {
+        let extracted = extract_emphasis("No markup at all.");
+        assert_eq!(extracted.body, "No markup at all.");
+        assert!(extracted.spans.is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+//! Heading announcement for TTS.
+//!
+//! Flattened headings read as if they were body prose, so document
+//! structure is inaudible. This rewrite runs on the markup before
+//! flattening and shapes `<h1>`–`<h6>` into something the reading loop
+//! segments as its own sentence, optionally with a spoken prefix.
+
+use super::ruby::find_tag;
+
+/// How headings sound. A paragraph break after the heading produces a
+/// natural pause in the sentence-segmented reading loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeadingStyle {
+    /// Unwrap the tags and read the heading inline like body text.
+    Off,
+    /// Isolate the heading as its own sentence with a pause after it.
+    #[default]
+    Pause,
+    /// Like `Pause`, with a spoken "Heading:" prefix for listeners who
+    /// want the structure called out explicitly.
+    SpokenPrefix,
+}
+
+/// Rewrite `<h1>`–`<h6>` elements of `html` according to `style`.
+pub fn rewrite_headings(html: &str, style: HeadingStyle) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some((level, open)) = next_heading(rest) {
+        out.push_str(&rest[..open.start]);
+        let after_open = &rest[open.end..];
+        let closing = format!("/h{level}");
+        let Some(close) = find_tag(after_open, &closing) else {
+            out.push_str(after_open);
+            return out;
+        };
+        let heading = after_open[..close.start].trim();
+        if !heading.is_empty() {
+            match style {
+                HeadingStyle::Off => out.push_str(heading),
+                HeadingStyle::Pause => out.push_str(&format!("{heading}.\n\n")),
+                HeadingStyle::SpokenPrefix => {
+                    out.push_str(&format!("Heading: {heading}.\n\n"));
+                }
+            }
+        }
+        rest = &after_open[close.end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Earliest heading opening tag and its level.
+fn next_heading(html: &str) -> Option<(u8, std::ops::Range<usize>)> {
+    (1u8..=6)
+        .filter_map(|level| find_tag(html, &format!("h{level}")).map(|range| (level, range)))
+        .min_by_key(|(_, range)| range.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::sentence_segments;
+
+    #[test]
+    fn headings_segment_as_their_own_sentences() {
+        let html = "<h2>Chapter One</h2><p>It begins here.</p>";
+        let flat = rewrite_headings(html, HeadingStyle::Pause);
+        assert_eq!(flat, "Chapter One.\n\n<p>It begins here.</p>");
+        let segments = sentence_segments(&flat.replace("<p>", "").replace("</p>", ""));
+        assert_eq!(segments[0].text, "Chapter One.");
+    }
+
+    #[test]
+    fn spoken_prefix_calls_out_the_structure() {
+        assert_eq!(
+            rewrite_headings("<h1>Results</h1>", HeadingStyle::SpokenPrefix),
+            "Heading: Results.\n\n"
+        );
+    }
+
+    #[test]
+    fn off_reads_the_heading_inline() {
+        assert_eq!(
+            rewrite_headings("<h3>Notes</h3> follow.", HeadingStyle::Off),
+            "Notes follow."
+        );
+    }
+}
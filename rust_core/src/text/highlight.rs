@@ -0,0 +1,99 @@
+//! Styled runs for rendering search matches inside a sentence.
+//!
+//! Search reports body-global byte ranges; the view renders whole
+//! sentences. This maps the two together: a sentence plus the match
+//! ranges becomes an ordered list of runs, each plain or highlighted,
+//! that the UI styles directly. Dismissing the search just re-renders
+//! with no ranges, which yields a single plain run.
+
+use std::ops::Range;
+
+use super::segment::SentenceSegment;
+
+/// One contiguous piece of a sentence with uniform styling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyledRun {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+/// Split `sentence` into runs around the `matches` that fall inside it.
+/// Ranges are byte offsets into the section body (the same space as
+/// `SentenceSegment::range`); ranges outside the sentence are ignored,
+/// and ones straddling its edge are clipped. Empty text yields no runs.
+pub fn sentence_runs(sentence: &SentenceSegment, matches: &[Range<usize>]) -> Vec<StyledRun> {
+    let mut inside: Vec<Range<usize>> = matches
+        .iter()
+        .map(|m| m.start.max(sentence.range.start)..m.end.min(sentence.range.end))
+        .filter(|m| m.start < m.end)
+        .collect();
+    inside.sort_by_key(|m| m.start);
+
+    let mut runs = Vec::new();
+    let mut cursor = sentence.range.start;
+    let push = |range: Range<usize>, highlighted: bool, runs: &mut Vec<StyledRun>| {
+        if range.start < range.end {
+            let local = range.start - sentence.range.start..range.end - sentence.range.start;
+            runs.push(StyledRun {
+                text: sentence.text[local].to_string(),
+                highlighted,
+            });
+        }
+    };
+    for matched in inside {
+        // Overlapping matches merge into the preceding highlight.
+        let start = matched.start.max(cursor);
+        push(cursor..start, false, &mut runs);
+        push(start..matched.end.max(cursor), true, &mut runs);
+        cursor = cursor.max(matched.end);
+    }
+    push(cursor..sentence.range.end, false, &mut runs);
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::sentence_segments;
+
+    #[test]
+    fn each_match_in_a_sentence_gets_its_own_run() {
+        let body = "The whale saw another whale. Calm seas.";
+        let sentences = sentence_segments(body);
+        // Both occurrences of "whale" in the first sentence.
+        let matches = vec![4..9, 22..27];
+
+        let runs = sentence_runs(&sentences[0], &matches);
+        let rendered: Vec<(&str, bool)> = runs
+            .iter()
+            .map(|run| (run.text.as_str(), run.highlighted))
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                ("The ", false),
+                ("whale", true),
+                (" saw another ", false),
+                ("whale", true),
+                (".", false),
+            ]
+        );
+        // The second sentence holds neither match: one plain run.
+        assert_eq!(
+            sentence_runs(&sentences[1], &matches),
+            vec![StyledRun {
+                text: "Calm seas.".into(),
+                highlighted: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn dismissed_search_renders_a_single_plain_run() {
+        let sentences = sentence_segments("Nothing to see.");
+        let runs = sentence_runs(&sentences[0], &[]);
+        assert_eq!(runs.len(), 1);
+        assert!(!runs[0].highlighted);
+        assert_eq!(runs[0].text, "Nothing to see.");
+    }
+}
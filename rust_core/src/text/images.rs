@@ -0,0 +1,58 @@
+//! Image alt-text announcement for TTS.
+//!
+//! Flattened HTML drops `<img>` elements silently, so listeners never
+//! learn an illustration was there. This rewrite replaces each image
+//! with a spoken-friendly "Image: <alt>." sentence (or removes it when
+//! the toggle is off), before the markup is flattened for reading.
+
+use super::ruby::find_tag;
+
+/// Replace `<img>` tags in `html` with their alt-text announcements.
+/// With `announce` off, or for images without a usable description, the
+/// tag is simply removed.
+pub fn rewrite_images(html: &str, announce: bool) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(tag) = find_tag(rest, "img") {
+        out.push_str(&rest[..tag.start]);
+        if announce {
+            if let Some(alt) = attribute_value(&rest[tag.clone()], "alt") {
+                let alt = alt.trim();
+                if !alt.is_empty() {
+                    out.push_str(&format!("Image: {alt}."));
+                }
+            }
+        }
+        rest = &rest[tag.end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn attribute_value(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let rest = &tag[start..];
+    Some(rest[..rest.find('"')?].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alt_text_becomes_a_spoken_sentence() {
+        let html = r#"Before. <img src="fig1.png" alt="A map of the island"/> After."#;
+        assert_eq!(
+            rewrite_images(html, true),
+            "Before. Image: A map of the island. After."
+        );
+    }
+
+    #[test]
+    fn images_vanish_when_disabled_or_undescribed() {
+        let html = r#"<img src="decoration.png" alt=""/>Text."#;
+        assert_eq!(rewrite_images(html, true), "Text.");
+        let described = r#"<img src="fig.png" alt="A figure"/>Text."#;
+        assert_eq!(rewrite_images(described, false), "Text.");
+    }
+}
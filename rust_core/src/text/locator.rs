@@ -0,0 +1,75 @@
+//! Resolution of EPUB-style locators (href plus optional anchor) to
+//! section indexes, for footnote links and external deep links.
+
+use std::collections::HashMap;
+
+/// Map from normalized locator to section index, built while loading a
+/// book's sections. Lookups fall back from `href#anchor` to the bare
+/// `href`, so a link to an anchor mid-file still lands on the right
+/// section even when only the file itself was registered.
+#[derive(Debug, Default, Clone)]
+pub struct LocatorMap {
+    sections: HashMap<String, usize>,
+}
+
+impl LocatorMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, locator: &str, section: usize) {
+        self.sections.insert(normalize_locator(locator), section);
+    }
+
+    /// Section index for `raw`, or `None` for an unknown locator (the
+    /// caller should leave the view where it is rather than jump).
+    pub fn resolve(&self, raw: &str) -> Option<usize> {
+        let normalized = normalize_locator(raw);
+        if let Some(&section) = self.sections.get(&normalized) {
+            return Some(section);
+        }
+        // Drop the fragment and retry against the containing file.
+        let without_fragment = normalized.split('#').next()?;
+        if without_fragment != normalized {
+            return self.sections.get(without_fragment).copied();
+        }
+        None
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+}
+
+/// Normalize an href for matching: strip leading `./` segments and any
+/// percent-encoded spaces, and lowercase the path (zip entries are
+/// matched case-insensitively by most readers).
+pub fn normalize_locator(raw: &str) -> String {
+    let trimmed = raw.trim().trim_start_matches("./");
+    trimmed.replace("%20", " ").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_exact_and_file_level_locators() {
+        let mut map = LocatorMap::new();
+        map.insert("OEBPS/chapter03.xhtml", 3);
+        map.insert("OEBPS/chapter03.xhtml#note-7", 4);
+
+        assert_eq!(map.resolve("oebps/chapter03.xhtml"), Some(3));
+        assert_eq!(map.resolve("./OEBPS/chapter03.xhtml#note-7"), Some(4));
+        // Unregistered anchor falls back to its file.
+        assert_eq!(map.resolve("OEBPS/chapter03.xhtml#other"), Some(3));
+        assert_eq!(map.resolve("OEBPS/missing.xhtml"), None);
+    }
+
+    #[test]
+    fn normalization_handles_encoded_spaces() {
+        let mut map = LocatorMap::new();
+        map.insert("Text/My Chapter.xhtml", 1);
+        assert_eq!(map.resolve("text/My%20Chapter.xhtml"), Some(1));
+    }
+}
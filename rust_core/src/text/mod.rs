@@ -0,0 +1,38 @@
+//! Text processing for the reading pipeline: segmentation, timing, and
+//! navigation.
+
+pub mod columns;
+pub mod emphasis;
+pub mod headings;
+pub mod highlight;
+pub mod images;
+pub mod locator;
+pub mod nav;
+pub mod normalize;
+pub mod pages;
+pub mod ruby;
+pub mod sections;
+pub mod segment;
+pub mod skip;
+pub mod timing;
+
+pub use columns::{reorder_columns, PositionedText};
+pub use emphasis::{extract_emphasis, EmphasizedText};
+pub use headings::{rewrite_headings, HeadingStyle};
+pub use highlight::{sentence_runs, StyledRun};
+pub use images::rewrite_images;
+pub use locator::{normalize_locator, LocatorMap};
+pub use nav::{percent_for_sentence, sentence_index_for_percent};
+pub use normalize::{normalize_for_speech, NormalizeOptions, NormalizedText};
+pub use pages::{page_for_sentence, paginate, SectionPage};
+pub use ruby::{rewrite_ruby, RubyMode};
+pub use sections::{
+    extract_all_with_cancel, ExtractOutcome, LazySections, SectionDescriptor, SectionSource,
+    TextSection,
+};
+pub use segment::{sentence_segments, SentenceSegment};
+pub use skip::{find_skippable_spans, spoken_body, SkipKind, SkipOptions, SkippableSpan};
+pub use timing::{
+    compute_word_weights, rescale_remaining_word_durations, SentenceTiming, TimingConfig,
+    TimingLog, WordWeighting,
+};
@@ -0,0 +1,48 @@
+//! Coarse position navigation: mapping between a 0–100 percentage and a
+//! sentence index, for the reader's "go to %" control.
+
+/// Sentence index closest to `percent` of the way through
+/// `total_sentences`. Out-of-range input is clamped, so 0 always lands on
+/// the first sentence and 100 on the last.
+pub fn sentence_index_for_percent(total_sentences: usize, percent: f64) -> usize {
+    if total_sentences == 0 {
+        return 0;
+    }
+    let fraction = (percent.clamp(0.0, 100.0)) / 100.0;
+    let index = (fraction * (total_sentences - 1) as f64).round() as usize;
+    index.min(total_sentences - 1)
+}
+
+/// Inverse mapping, for updating the displayed progress after a jump.
+pub fn percent_for_sentence(total_sentences: usize, sentence: usize) -> f64 {
+    if total_sentences < 2 {
+        return 0.0;
+    }
+    let clamped = sentence.min(total_sentences - 1);
+    clamped as f64 / (total_sentences - 1) as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_maps_to_clamped_sentence_index() {
+        assert_eq!(sentence_index_for_percent(0, 50.0), 0);
+        assert_eq!(sentence_index_for_percent(200, 0.0), 0);
+        assert_eq!(sentence_index_for_percent(200, 100.0), 199);
+        assert_eq!(sentence_index_for_percent(200, 50.0), 100);
+        // Clamped, not wrapped.
+        assert_eq!(sentence_index_for_percent(200, 150.0), 199);
+        assert_eq!(sentence_index_for_percent(200, -3.0), 0);
+    }
+
+    #[test]
+    fn round_trips_within_a_sentence() {
+        let total = 137;
+        for sentence in [0, 1, 68, 135, 136] {
+            let percent = percent_for_sentence(total, sentence);
+            assert_eq!(sentence_index_for_percent(total, percent), sentence);
+        }
+    }
+}
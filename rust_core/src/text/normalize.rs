@@ -0,0 +1,238 @@
+//! Spoken-form normalization for TTS input.
+//!
+//! Engines like flite and espeak read "1999" and "$3.50" inconsistently,
+//! so numbers, years, currency, and common abbreviations are expanded
+//! into words before synthesis. The display text is untouched; a word
+//! map ties every spoken word back to the original token so the
+//! highlight stays aligned when one token expands into several words.
+
+/// Which expansions run. Everything defaults on; each can be disabled
+/// independently from settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    pub expand_numbers: bool,
+    pub expand_currency: bool,
+    pub expand_abbreviations: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            expand_numbers: true,
+            expand_currency: true,
+            expand_abbreviations: true,
+        }
+    }
+}
+
+/// The normalized text plus, for each whitespace-separated word of it,
+/// the index of the original word it came from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NormalizedText {
+    pub text: String,
+    /// `word_map[i]` is the original word index behind spoken word `i`.
+    /// Identity when nothing expanded.
+    pub word_map: Vec<usize>,
+}
+
+/// English spellings of `Mr.`-style abbreviations the engines tend to
+/// spell out letter by letter.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("mr", "mister"),
+    ("mrs", "missus"),
+    ("ms", "miz"),
+    ("dr", "doctor"),
+    ("st", "saint"),
+    ("prof", "professor"),
+    ("vs", "versus"),
+    ("etc", "et cetera"),
+];
+
+/// Expand `text` into its spoken form. Tokens that need no expansion
+/// pass through unchanged, including their punctuation.
+pub fn normalize_for_speech(text: &str, options: &NormalizeOptions) -> NormalizedText {
+    let mut out = String::with_capacity(text.len());
+    let mut word_map = Vec::new();
+    for (index, token) in text.split_whitespace().enumerate() {
+        let expanded = expand_token(token, options).unwrap_or_else(|| token.to_string());
+        for word in expanded.split_whitespace() {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(word);
+            word_map.push(index);
+        }
+    }
+    NormalizedText {
+        text: out,
+        word_map,
+    }
+}
+
+/// Spoken form of one token, or `None` when it should pass through.
+fn expand_token(token: &str, options: &NormalizeOptions) -> Option<String> {
+    let trailing: String = token
+        .chars()
+        .rev()
+        .take_while(|c| !c.is_alphanumeric())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let core = &token[..token.len() - trailing.len()];
+
+    if options.expand_currency && core.starts_with('$') {
+        return expand_currency(&core[1..]).map(|spoken| format!("{spoken}{trailing}"));
+    }
+    if options.expand_abbreviations && trailing.starts_with('.') {
+        let lower = core.to_lowercase();
+        if let Some((_, expansion)) = ABBREVIATIONS.iter().find(|(abbr, _)| *abbr == lower) {
+            // The abbreviating period goes with the abbreviation; keep
+            // any punctuation after it ("etc.," -> "et cetera,").
+            return Some(format!("{expansion}{}", &trailing[1..]));
+        }
+    }
+    if options.expand_numbers {
+        return expand_number_token(core).map(|spoken| format!("{spoken}{trailing}"));
+    }
+    None
+}
+
+/// "3.50" -> "three dollars and fifty cents"; plain "5" -> "five
+/// dollars". Anything unparseable passes through.
+fn expand_currency(amount: &str) -> Option<String> {
+    let (dollars, cents) = match amount.split_once('.') {
+        Some((dollars, cents)) => (dollars, Some(cents)),
+        None => (amount, None),
+    };
+    let dollars: u64 = dollars.replace(',', "").parse().ok()?;
+    let unit = if dollars == 1 { "dollar" } else { "dollars" };
+    let mut spoken = format!("{} {unit}", number_to_words(dollars));
+    if let Some(cents) = cents {
+        let cents: u64 = cents.parse().ok()?;
+        if cents > 0 {
+            let unit = if cents == 1 { "cent" } else { "cents" };
+            spoken = format!("{spoken} and {} {unit}", number_to_words(cents));
+        }
+    }
+    Some(spoken)
+}
+
+/// Bare integers become words; four-digit values in the plausible year
+/// range read as year pairs ("1999" -> "nineteen ninety-nine").
+fn expand_number_token(core: &str) -> Option<String> {
+    if core.is_empty() || !core.chars().all(|c| c.is_ascii_digit() || c == ',') {
+        return None;
+    }
+    let digits = core.replace(',', "");
+    let value: u64 = digits.parse().ok()?;
+    if digits.len() == 4 && (1100..2100).contains(&value) {
+        return Some(year_to_words(value));
+    }
+    Some(number_to_words(value))
+}
+
+/// Years read as two pairs, with the "two thousand" decade special-cased
+/// since "twenty oh-nine" is not how people say it.
+fn year_to_words(year: u64) -> String {
+    let (high, low) = (year / 100, year % 100);
+    if (2000..2010).contains(&year) || low == 0 {
+        return number_to_words(year);
+    }
+    if low < 10 {
+        return format!("{} oh {}", number_to_words(high), number_to_words(low));
+    }
+    format!("{} {}", number_to_words(high), number_to_words(low))
+}
+
+const ONES: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+    "nineteen",
+];
+const TENS: &[&str] = &[
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// English words for an integer, up to the billions nonfiction actually
+/// uses.
+pub fn number_to_words(value: u64) -> String {
+    if value < 20 {
+        return ONES[value as usize].to_string();
+    }
+    if value < 100 {
+        let (tens, ones) = (value / 10, value % 10);
+        return if ones == 0 {
+            TENS[tens as usize].to_string()
+        } else {
+            format!("{}-{}", TENS[tens as usize], ONES[ones as usize])
+        };
+    }
+    for (scale, name) in [
+        (1_000_000_000, "billion"),
+        (1_000_000, "million"),
+        (1_000, "thousand"),
+        (100, "hundred"),
+    ] {
+        if value >= scale {
+            let (high, low) = (value / scale, value % scale);
+            let mut spoken = format!("{} {name}", number_to_words(high));
+            if low > 0 {
+                spoken = format!("{spoken} {}", number_to_words(low));
+            }
+            return spoken;
+        }
+    }
+    unreachable!("values below 100 are handled above")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_years_and_currency_expand_to_words() {
+        let normalized =
+            normalize_for_speech("In 1999 it cost $3.50, not 42.", &NormalizeOptions::default());
+        assert_eq!(
+            normalized.text,
+            "In nineteen ninety-nine it cost three dollars and fifty cents, not forty-two."
+        );
+    }
+
+    #[test]
+    fn word_map_ties_expansions_back_to_original_tokens() {
+        let normalized = normalize_for_speech("costs $3.50 today", &NormalizeOptions::default());
+        // "$3.50" (original word 1) became five spoken words.
+        assert_eq!(normalized.word_map, vec![0, 1, 1, 1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn abbreviations_expand_only_with_their_period() {
+        let options = NormalizeOptions::default();
+        assert_eq!(
+            normalize_for_speech("Dr. Watson", &options).text,
+            "doctor Watson"
+        );
+        // No period: "Dr" might be a name fragment; leave it alone.
+        assert_eq!(normalize_for_speech("Dr Watson", &options).text, "Dr Watson");
+    }
+
+    #[test]
+    fn disabled_passes_leave_tokens_untouched() {
+        let options = NormalizeOptions {
+            expand_numbers: false,
+            expand_currency: false,
+            expand_abbreviations: false,
+        };
+        let normalized = normalize_for_speech("1999 costs $3.50", &options);
+        assert_eq!(normalized.text, "1999 costs $3.50");
+        assert_eq!(normalized.word_map, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn large_numbers_read_with_scales() {
+        assert_eq!(number_to_words(1_000_001), "one million one");
+        assert_eq!(number_to_words(317), "three hundred seventeen");
+    }
+}
@@ -0,0 +1,91 @@
+//! Pagination for oversized sections.
+//!
+//! A single unsplit chapter (or a PDF extracted as one section) renders
+//! as one enormous scroll. Chunking its sentences into fixed-size pages
+//! keeps rendering cheap and the reader's place findable, while every
+//! sentence keeps its section-global index so TTS progress and resume
+//! positions are unaffected by where the page breaks fall.
+
+use super::segment::SentenceSegment;
+
+/// Sentences per page when the caller doesn't configure one.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// One page of a paginated section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionPage {
+    pub index: usize,
+    /// Section-global index of this page's first sentence; sentence `i`
+    /// on the page is global sentence `first_sentence + i`.
+    pub first_sentence: usize,
+    pub sentences: Vec<SentenceSegment>,
+}
+
+/// Chunk `sentences` into pages of `page_size`. A size of zero disables
+/// pagination and yields the whole section as one page; empty input
+/// yields no pages.
+pub fn paginate(sentences: Vec<SentenceSegment>, page_size: usize) -> Vec<SectionPage> {
+    if sentences.is_empty() {
+        return Vec::new();
+    }
+    let per_page = if page_size == 0 {
+        sentences.len()
+    } else {
+        page_size
+    };
+    let mut pages = Vec::with_capacity(sentences.len().div_ceil(per_page));
+    let mut rest = sentences;
+    while !rest.is_empty() {
+        let tail = rest.split_off(per_page.min(rest.len()));
+        pages.push(SectionPage {
+            index: pages.len(),
+            first_sentence: pages.len() * per_page,
+            sentences: rest,
+        });
+        rest = tail;
+    }
+    pages
+}
+
+/// The page holding section-global `sentence_index`, for jumping the
+/// view to wherever TTS or a restored position points.
+pub fn page_for_sentence(pages: &[SectionPage], sentence_index: usize) -> Option<usize> {
+    pages
+        .iter()
+        .find(|page| {
+            sentence_index >= page.first_sentence
+                && sentence_index < page.first_sentence + page.sentences.len()
+        })
+        .map(|page| page.index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::sentence_segments;
+
+    #[test]
+    fn pages_preserve_global_sentence_indices() {
+        let body = "One. Two. Three. Four. Five.";
+        let sentences = sentence_segments(body);
+        let pages = paginate(sentences.clone(), 2);
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[1].first_sentence, 2);
+        assert_eq!(pages[2].sentences.len(), 1);
+        // Sentence 3 is the second sentence of page 1, unchanged.
+        assert_eq!(pages[1].sentences[1], sentences[3]);
+        assert_eq!(page_for_sentence(&pages, 3), Some(1));
+        assert_eq!(page_for_sentence(&pages, 4), Some(2));
+        assert_eq!(page_for_sentence(&pages, 5), None);
+    }
+
+    #[test]
+    fn zero_page_size_keeps_the_section_whole() {
+        let sentences = sentence_segments("One. Two. Three.");
+        let pages = paginate(sentences, 0);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].sentences.len(), 3);
+        assert_eq!(paginate(Vec::new(), 0), Vec::new());
+    }
+}
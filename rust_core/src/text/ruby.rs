@@ -0,0 +1,129 @@
+//! Ruby (furigana) handling for Japanese HTML/EPUB content.
+//!
+//! Naive HTML flattening turns `<ruby>漢字<rt>かんじ</rt></ruby>` into
+//! "漢字かんじ" — the base text immediately followed by its reading —
+//! which garbles both the display and the spoken output. These rewrites
+//! run on the markup before flattening.
+
+/// What to do with the `<rt>` reading of a ruby annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RubyMode {
+    /// Keep only the base text; readings disappear entirely. This is the
+    /// right input for TTS, which must speak the base text once.
+    #[default]
+    Drop,
+    /// Keep the reading after the base text in parentheses, e.g.
+    /// "漢字（かんじ）", for display aimed at learners.
+    Parenthetical,
+}
+
+/// Rewrite the `<ruby>` elements of `html` according to `mode`,
+/// unwrapping the tags so downstream flattening sees plain text.
+/// `<rp>` fallback parentheses are always removed; markup without ruby
+/// passes through unchanged.
+pub fn rewrite_ruby(html: &str, mode: RubyMode) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(open) = find_tag(rest, "ruby") {
+        out.push_str(&rest[..open.start]);
+        let after_open = &rest[open.end..];
+        let Some(close) = find_tag(after_open, "/ruby") else {
+            // Unterminated ruby: emit the remainder untouched.
+            out.push_str(&rest[open.start..]);
+            return out;
+        };
+        out.push_str(&rewrite_ruby_body(&after_open[..close.start], mode));
+        rest = &after_open[close.end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Flatten the inside of one `<ruby>` element: base text stays, `<rp>`
+/// goes, `<rt>` goes or becomes a parenthetical per `mode`.
+fn rewrite_ruby_body(body: &str, mode: RubyMode) -> String {
+    let mut out = String::new();
+    let mut rest = body;
+    loop {
+        let rt = find_tag(rest, "rt");
+        let rp = find_tag(rest, "rp");
+        let next = match (&rt, &rp) {
+            (Some(rt), Some(rp)) => {
+                if rt.start < rp.start {
+                    ("rt", rt.clone())
+                } else {
+                    ("rp", rp.clone())
+                }
+            }
+            (Some(rt), None) => ("rt", rt.clone()),
+            (None, Some(rp)) => ("rp", rp.clone()),
+            (None, None) => {
+                out.push_str(rest);
+                return out;
+            }
+        };
+        let (kind, open) = next;
+        out.push_str(&rest[..open.start]);
+        let after_open = &rest[open.end..];
+        let closing = format!("/{kind}");
+        let Some(close) = find_tag(after_open, &closing) else {
+            return out;
+        };
+        if kind == "rt" && mode == RubyMode::Parenthetical {
+            let reading = after_open[..close.start].trim();
+            if !reading.is_empty() {
+                out.push_str(&format!("（{reading}）"));
+            }
+        }
+        rest = &after_open[close.end..];
+    }
+}
+
+/// Byte range of the next `<name ...>` tag, matching the tag name
+/// case-insensitively and only at a word boundary so `rt` doesn't match
+/// inside `ruby`. Shared with the emphasis scanner.
+pub(crate) fn find_tag(html: &str, name: &str) -> Option<std::ops::Range<usize>> {
+    let lower = html.to_lowercase();
+    let needle = format!("<{name}");
+    let mut from = 0usize;
+    while let Some(offset) = lower[from..].find(&needle) {
+        let start = from + offset;
+        let after = start + needle.len();
+        let boundary = lower[after..]
+            .chars()
+            .next()
+            .is_none_or(|c| c == '>' || c.is_whitespace() || c == '/');
+        if boundary {
+            let end = lower[after..].find('>').map(|i| after + i + 1)?;
+            return Some(start..end);
+        }
+        from = after;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "読む：<ruby>漢字<rp>（</rp><rt>かんじ</rt><rp>）</rp></ruby>です。";
+
+    #[test]
+    fn dropping_readings_leaves_only_the_base_text() {
+        assert_eq!(rewrite_ruby(SOURCE, RubyMode::Drop), "読む：漢字です。");
+    }
+
+    #[test]
+    fn parenthetical_mode_keeps_readings_for_learners() {
+        assert_eq!(
+            rewrite_ruby(SOURCE, RubyMode::Parenthetical),
+            "読む：漢字（かんじ）です。"
+        );
+    }
+
+    #[test]
+    fn markup_without_ruby_passes_through() {
+        let html = "<p>No annotations here.</p>";
+        assert_eq!(rewrite_ruby(html, RubyMode::Drop), html);
+    }
+}
@@ -0,0 +1,374 @@
+//! Section-level text extraction: the interface between format loaders
+//! (PDF, EPUB, plain text) and the reader, including cancellable bulk
+//! extraction and lazy per-section loading for formats where pulling
+//! text is expensive.
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+
+use crate::engine::CancelToken;
+
+/// One readable section (a chapter or a PDF page) of a book.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextSection {
+    pub index: usize,
+    pub title: Option<String>,
+    pub body: String,
+}
+
+/// A format loader that can report how many sections a book has and
+/// extract them one at a time, so callers control pacing and can stop
+/// between sections.
+pub trait SectionSource: Send + Sync {
+    fn section_count(&self) -> usize;
+
+    /// Extract one section's body. Expensive for PDFs; called at most
+    /// once per section by the caching layers above.
+    fn extract(&self, index: usize) -> Result<String, String>;
+
+    /// Cheap display title for a section, available without extracting
+    /// the body. Defaults to `None` (callers show "Page N" style labels).
+    fn title(&self, _index: usize) -> Option<String> {
+        None
+    }
+}
+
+/// What a bulk extraction produced: everything extracted so far plus
+/// whether it stopped early on a cancel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractOutcome {
+    pub sections: Vec<TextSection>,
+    pub cancelled: bool,
+}
+
+/// Extract every section of `source` in order, checking `cancel` between
+/// sections so closing the reader aborts promptly instead of grinding
+/// through the rest of a huge file. `progress(done, total)` fires after
+/// each section. Failed sections become empty bodies rather than
+/// aborting the run, matching how a partly corrupt book should still
+/// open.
+pub fn extract_all_with_cancel(
+    source: &dyn SectionSource,
+    cancel: &CancelToken,
+    mut progress: impl FnMut(usize, usize),
+) -> ExtractOutcome {
+    let total = source.section_count();
+    let mut sections = Vec::with_capacity(total);
+    for index in 0..total {
+        if cancel.is_cancelled() {
+            return ExtractOutcome {
+                sections,
+                cancelled: true,
+            };
+        }
+        let body = source.extract(index).unwrap_or_else(|err| {
+            tracing::warn!(index, %err, "section extraction failed; leaving it empty");
+            String::new()
+        });
+        sections.push(TextSection {
+            index,
+            title: source.title(index),
+            body,
+        });
+        progress(index + 1, total);
+    }
+    ExtractOutcome {
+        sections,
+        cancelled: false,
+    }
+}
+
+/// What the chapter list shows before any body has been extracted:
+/// just the section's index and whatever cheap title the source knows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDescriptor {
+    pub index: usize,
+    pub title: Option<String>,
+}
+
+/// Lazy view over a [`SectionSource`]: opening a book only asks the
+/// source how many sections exist, and each body is extracted the first
+/// time it's requested, then served from an in-memory cache. This is
+/// what keeps a 1,000-page PDF fast to open — nothing is extracted
+/// until the reader actually lands on a page.
+pub struct LazySections {
+    source: Box<dyn SectionSource>,
+    /// `None` caps nothing; `Some(n)` keeps at most the `n` most
+    /// recently used bodies resident.
+    capacity: Option<usize>,
+    cache: Mutex<SectionCache>,
+}
+
+/// Cache state behind one lock: resident bodies, their use order
+/// (least recent first), and which section the reader is currently on.
+#[derive(Default)]
+struct SectionCache {
+    entries: HashMap<usize, String>,
+    recency: Vec<usize>,
+    active: Option<usize>,
+}
+
+impl SectionCache {
+    fn touch(&mut self, index: usize) {
+        self.recency.retain(|&i| i != index);
+        self.recency.push(index);
+    }
+
+    /// Drop least-recently-used bodies down to `capacity`, never the
+    /// active section — evicting the page on screen would immediately
+    /// re-extract it.
+    fn evict_to(&mut self, capacity: usize) {
+        while self.entries.len() > capacity {
+            let Some(position) = self
+                .recency
+                .iter()
+                .position(|&i| Some(i) != self.active)
+            else {
+                return;
+            };
+            let index = self.recency.remove(position);
+            self.entries.remove(&index);
+        }
+    }
+}
+
+impl LazySections {
+    pub fn new(source: Box<dyn SectionSource>) -> Self {
+        Self {
+            source,
+            capacity: None,
+            cache: Mutex::new(SectionCache::default()),
+        }
+    }
+
+    /// Like [`LazySections::new`], but at most `capacity` section
+    /// bodies stay decoded in memory; older ones re-materialize on
+    /// revisit. Bounds a long session in a big PDF or image-heavy EPUB.
+    pub fn with_capacity(source: Box<dyn SectionSource>, capacity: usize) -> Self {
+        Self {
+            source,
+            capacity: Some(capacity.max(1)),
+            cache: Mutex::new(SectionCache::default()),
+        }
+    }
+
+    pub fn section_count(&self) -> usize {
+        self.source.section_count()
+    }
+
+    /// The section's body, extracting it on first access. Extraction
+    /// failures are handled like the bulk path: the section comes back
+    /// empty (and stays cached empty) rather than erroring the reader.
+    pub fn body(&self, index: usize) -> String {
+        {
+            let mut cache = self.cache.lock();
+            if let Some(cached) = cache.entries.get(&index) {
+                let cached = cached.clone();
+                cache.touch(index);
+                return cached;
+            }
+        }
+        let body = self.source.extract(index).unwrap_or_else(|err| {
+            tracing::warn!(index, %err, "lazy section extraction failed; leaving it empty");
+            String::new()
+        });
+        let mut cache = self.cache.lock();
+        cache.entries.insert(index, body.clone());
+        cache.touch(index);
+        if let Some(capacity) = self.capacity {
+            cache.evict_to(capacity);
+        }
+        body
+    }
+
+    /// Descriptors for every section, for populating the chapter list
+    /// without extracting a single body.
+    pub fn descriptors(&self) -> Vec<SectionDescriptor> {
+        (0..self.source.section_count())
+            .map(|index| SectionDescriptor {
+                index,
+                title: self.source.title(index),
+            })
+            .collect()
+    }
+
+    /// Materialize the section the reader just selected. Indexes past
+    /// the end return `None`; everything else goes through the same
+    /// extract-once cache as [`LazySections::body`].
+    pub fn select(&self, index: usize) -> Option<TextSection> {
+        if index >= self.source.section_count() {
+            return None;
+        }
+        // Mark active before extraction so the eviction pass can never
+        // pick the section the reader is switching to.
+        self.cache.lock().active = Some(index);
+        Some(TextSection {
+            index,
+            title: self.source.title(index),
+            body: self.body(index),
+        })
+    }
+
+    /// How many section bodies are currently held in memory.
+    pub fn loaded(&self) -> usize {
+        self.cache.lock().entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) struct StubSource {
+        pub pages: Vec<&'static str>,
+    }
+
+    impl SectionSource for StubSource {
+        fn section_count(&self) -> usize {
+            self.pages.len()
+        }
+
+        fn extract(&self, index: usize) -> Result<String, String> {
+            match self.pages[index] {
+                "ERR" => Err("bad page".into()),
+                body => Ok(body.to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn extraction_reports_progress_and_tolerates_bad_sections() {
+        let source = StubSource {
+            pages: vec!["one", "ERR", "three"],
+        };
+        let mut seen = Vec::new();
+        let outcome = extract_all_with_cancel(&source, &CancelToken::new(), |done, total| {
+            seen.push((done, total))
+        });
+        assert!(!outcome.cancelled);
+        assert_eq!(outcome.sections.len(), 3);
+        assert_eq!(outcome.sections[1].body, "");
+        assert_eq!(seen, vec![(1, 3), (2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn lazy_sections_extract_once_on_demand() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct CountingSource {
+            inner: StubSource,
+            extracted: Arc<AtomicUsize>,
+        }
+
+        impl SectionSource for CountingSource {
+            fn section_count(&self) -> usize {
+                self.inner.section_count()
+            }
+
+            fn extract(&self, index: usize) -> Result<String, String> {
+                self.extracted.fetch_add(1, Ordering::SeqCst);
+                self.inner.extract(index)
+            }
+        }
+
+        let extracted = Arc::new(AtomicUsize::new(0));
+        let lazy = LazySections::new(Box::new(CountingSource {
+            inner: StubSource {
+                pages: vec!["one", "ERR", "three"],
+            },
+            extracted: Arc::clone(&extracted),
+        }));
+
+        // Opening costs nothing: only the page count is known.
+        assert_eq!(lazy.section_count(), 3);
+        assert_eq!(extracted.load(Ordering::SeqCst), 0);
+
+        assert_eq!(lazy.body(2), "three");
+        assert_eq!(lazy.body(2), "three");
+        assert_eq!(extracted.load(Ordering::SeqCst), 1);
+
+        // Failures cache as empty instead of retrying every visit.
+        assert_eq!(lazy.body(1), "");
+        assert_eq!(lazy.body(1), "");
+        assert_eq!(extracted.load(Ordering::SeqCst), 2);
+        assert_eq!(lazy.loaded(), 2);
+    }
+
+    #[test]
+    fn capped_cache_evicts_least_recent_but_never_the_active_section() {
+        let lazy = LazySections::with_capacity(
+            Box::new(StubSource {
+                pages: vec!["one", "two", "three", "four"],
+            }),
+            2,
+        );
+
+        lazy.select(0);
+        lazy.select(1);
+        lazy.select(2);
+        assert_eq!(lazy.loaded(), 2);
+
+        // Section 0 was evicted; reading it re-extracts, and with the
+        // reader still on section 2, it's section 1 that gets dropped.
+        lazy.body(0);
+        assert_eq!(lazy.loaded(), 2);
+        lazy.body(3);
+        assert_eq!(lazy.loaded(), 2);
+        // Active section 2 survived both evictions despite being the
+        // least recently touched entry.
+        assert_eq!(lazy.select(2).unwrap().body, "three");
+        assert_eq!(lazy.loaded(), 2);
+    }
+
+    #[test]
+    fn descriptors_list_without_extracting_and_select_materializes() {
+        struct TitledSource(StubSource);
+
+        impl SectionSource for TitledSource {
+            fn section_count(&self) -> usize {
+                self.0.section_count()
+            }
+
+            fn extract(&self, index: usize) -> Result<String, String> {
+                self.0.extract(index)
+            }
+
+            fn title(&self, index: usize) -> Option<String> {
+                Some(format!("Chapter {}", index + 1))
+            }
+        }
+
+        let lazy = LazySections::new(Box::new(TitledSource(StubSource {
+            pages: vec!["one", "two"],
+        })));
+
+        let descriptors = lazy.descriptors();
+        assert_eq!(descriptors.len(), 2);
+        assert_eq!(descriptors[1].title.as_deref(), Some("Chapter 2"));
+        assert_eq!(lazy.loaded(), 0);
+
+        let selected = lazy.select(1).unwrap();
+        assert_eq!(selected.title.as_deref(), Some("Chapter 2"));
+        assert_eq!(selected.body, "two");
+        assert_eq!(lazy.loaded(), 1);
+        assert_eq!(lazy.select(2), None);
+    }
+
+    #[test]
+    fn cancelling_stops_between_sections_with_partial_output() {
+        let source = StubSource {
+            pages: vec!["one", "two", "three"],
+        };
+        let cancel = CancelToken::new();
+        let outcome = extract_all_with_cancel(&source, &cancel, |done, _| {
+            if done == 1 {
+                cancel.cancel();
+            }
+        });
+        assert!(outcome.cancelled);
+        assert_eq!(outcome.sections.len(), 1);
+        assert_eq!(outcome.sections[0].body, "one");
+    }
+}
@@ -0,0 +1,162 @@
+//! Sentence segmentation with byte-accurate offsets for highlighting.
+
+use std::ops::Range;
+
+/// One sentence of a section body. All ranges are byte offsets into the
+/// original body on UTF-8 character boundaries, so the reader can map a
+/// tapped position to a word and scroll it into view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentenceSegment {
+    pub text: String,
+    /// Byte range of the sentence within the body.
+    pub range: Range<usize>,
+    /// Byte range of each word within the body, in reading order.
+    pub words: Vec<Range<usize>>,
+}
+
+const SENTENCE_TERMINATORS: &[char] = &['.', '!', '?'];
+
+/// Fullwidth terminators used by Chinese/Japanese text. These end a
+/// sentence unconditionally: CJK prose has no space after punctuation.
+const CJK_SENTENCE_TERMINATORS: &[char] = &['。', '！', '？'];
+
+/// Split `body` into sentences on terminator-plus-whitespace boundaries
+/// (and paragraph breaks), recording per-word byte ranges as it goes.
+/// CJK text segments on its fullwidth terminators and tokenizes
+/// per-character, since it has no word-separating spaces.
+pub fn sentence_segments(body: &str) -> Vec<SentenceSegment> {
+    let mut segments = Vec::new();
+    let mut start = 0usize;
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((idx, ch)) = chars.next() {
+        let ends_here = if SENTENCE_TERMINATORS.contains(&ch) {
+            // Only end on terminator-plus-whitespace (or end of text), so
+            // "3.14" and "..." don't split mid-token.
+            match chars.peek() {
+                None => true,
+                Some((_, next)) => next.is_whitespace(),
+            }
+        } else if CJK_SENTENCE_TERMINATORS.contains(&ch) {
+            true
+        } else {
+            ch == '\n' && matches!(chars.peek(), Some((_, '\n')))
+        };
+        if ends_here {
+            let end = idx + ch.len_utf8();
+            push_segment(body, start..end, &mut segments);
+            start = end;
+        }
+    }
+    push_segment(body, start..body.len(), &mut segments);
+    segments
+}
+
+fn push_segment(body: &str, range: Range<usize>, segments: &mut Vec<SentenceSegment>) {
+    let raw = &body[range.clone()];
+    if raw.trim().is_empty() {
+        return;
+    }
+    // Tighten the range to the trimmed sentence.
+    let leading = raw.len() - raw.trim_start().len();
+    let trailing = raw.len() - raw.trim_end().len();
+    let range = range.start + leading..range.end - trailing;
+    let text = body[range.clone()].to_string();
+    let words = word_ranges(body, range.clone());
+    segments.push(SentenceSegment { text, range, words });
+}
+
+/// Word ranges within `range`, as byte offsets into the whole body.
+/// Latin-style text splits on whitespace; CJK characters each become
+/// their own "word" so the highlight can step through unspaced prose.
+fn word_ranges(body: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let slice = &body[range.clone()];
+    let mut words = Vec::new();
+    let mut word_start: Option<usize> = None;
+    for (idx, ch) in slice.char_indices() {
+        if ch.is_whitespace() {
+            if let Some(start) = word_start.take() {
+                words.push(range.start + start..range.start + idx);
+            }
+        } else if is_cjk(ch) {
+            // Close any pending Latin run, then emit the character alone.
+            if let Some(start) = word_start.take() {
+                words.push(range.start + start..range.start + idx);
+            }
+            words.push(range.start + idx..range.start + idx + ch.len_utf8());
+        } else if word_start.is_none() {
+            word_start = Some(idx);
+        }
+    }
+    if let Some(start) = word_start {
+        words.push(range.start + start..range.end);
+    }
+    words
+}
+
+/// Ideographs, kana, and hangul — scripts written without word spaces.
+/// Fullwidth punctuation is deliberately excluded: it groups like any
+/// other non-space character instead of being split per glyph.
+fn is_cjk(ch: char) -> bool {
+    matches!(ch,
+        '\u{3400}'..='\u{4DBF}'   // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{3040}'..='\u{309F}' // Hiragana
+        | '\u{30A0}'..='\u{30FF}' // Katakana
+        | '\u{AC00}'..='\u{D7AF}' // Hangul syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_sentences_and_words_with_offsets() {
+        let body = "Hello there. How are you?";
+        let segments = sentence_segments(body);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Hello there.");
+        assert_eq!(&body[segments[0].words[1].clone()], "there.");
+        assert_eq!(segments[1].text, "How are you?");
+        assert_eq!(&body[segments[1].words[2].clone()], "you?");
+    }
+
+    #[test]
+    fn ranges_stay_on_utf8_boundaries() {
+        let body = "Der Weg ist schön. Καλημέρα σου λέω!";
+        let segments = sentence_segments(body);
+        assert_eq!(segments.len(), 2);
+        for segment in &segments {
+            for word in &segment.words {
+                // Slicing panics if a range is off a char boundary.
+                let _ = &body[word.clone()];
+            }
+        }
+        assert_eq!(&body[segments[1].words[0].clone()], "Καλημέρα");
+    }
+
+    #[test]
+    fn cjk_text_splits_on_fullwidth_terminators_per_character() {
+        let body = "今日は良い天気。明日はRustを勉強します！";
+        let segments = sentence_segments(body);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "今日は良い天気。");
+        // Each ideograph/kana is its own highlightable token; the
+        // embedded Latin word stays whole.
+        assert_eq!(&body[segments[0].words[0].clone()], "今");
+        assert!(segments[1]
+            .words
+            .iter()
+            .any(|word| &body[word.clone()] == "Rust"));
+    }
+
+    #[test]
+    fn paragraph_breaks_end_sentences() {
+        let body = "A heading without punctuation\n\nBody text follows.";
+        let segments = sentence_segments(body);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "A heading without punctuation");
+    }
+}
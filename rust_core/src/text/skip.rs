@@ -0,0 +1,142 @@
+//! Spans the TTS path should not read verbatim: fenced code blocks and
+//! bare URLs. They stay visible in the text view; only the spoken form
+//! replaces them with a short announcement.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipKind {
+    CodeBlock,
+    Url,
+}
+
+/// One span of the body that can be announced instead of read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippableSpan {
+    pub range: Range<usize>,
+    pub kind: SkipKind,
+}
+
+/// Which announcements are active. Both default on; either can be
+/// disabled to hear the raw text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkipOptions {
+    pub skip_code_blocks: bool,
+    pub skip_urls: bool,
+}
+
+impl Default for SkipOptions {
+    fn default() -> Self {
+        Self {
+            skip_code_blocks: true,
+            skip_urls: true,
+        }
+    }
+}
+
+/// Spoken replacements. Short declarative phrases so the listener knows
+/// something was elided without losing the reading rhythm.
+const CODE_ANNOUNCEMENT: &str = "Code block.";
+const URL_ANNOUNCEMENT: &str = "link";
+
+/// Find fenced code blocks (``` ... ```) and bare `http(s)://`/`www.`
+/// URLs in `body`, in document order. An unterminated fence runs to the
+/// end of the body, matching how Markdown renderers treat it.
+pub fn find_skippable_spans(body: &str) -> Vec<SkippableSpan> {
+    let mut spans = Vec::new();
+    let mut cursor = 0usize;
+    while let Some(offset) = body[cursor..].find("```") {
+        let start = cursor + offset;
+        let after = start + 3;
+        let end = match body[after..].find("```") {
+            Some(close) => after + close + 3,
+            None => body.len(),
+        };
+        spans.push(SkippableSpan {
+            range: start..end,
+            kind: SkipKind::CodeBlock,
+        });
+        cursor = end;
+    }
+
+    for prefix in ["http://", "https://", "www."] {
+        let mut cursor = 0usize;
+        while let Some(offset) = body[cursor..].find(prefix) {
+            let start = cursor + offset;
+            let end = start
+                + body[start..]
+                    .find(char::is_whitespace)
+                    .unwrap_or(body.len() - start);
+            cursor = end;
+            // URLs inside a code block are already covered by it.
+            if spans
+                .iter()
+                .any(|span| span.range.start <= start && end <= span.range.end)
+            {
+                continue;
+            }
+            spans.push(SkippableSpan {
+                range: start..end,
+                kind: SkipKind::Url,
+            });
+        }
+    }
+    spans.sort_by_key(|span| span.range.start);
+    spans
+}
+
+/// The body with skippable spans replaced by their announcements, ready
+/// for synthesis. Disabled kinds keep their original text.
+pub fn spoken_body(body: &str, spans: &[SkippableSpan], options: &SkipOptions) -> String {
+    let mut out = String::with_capacity(body.len());
+    let mut cursor = 0usize;
+    for span in spans {
+        let replacement = match span.kind {
+            SkipKind::CodeBlock if options.skip_code_blocks => CODE_ANNOUNCEMENT,
+            SkipKind::Url if options.skip_urls => URL_ANNOUNCEMENT,
+            _ => continue,
+        };
+        out.push_str(&body[cursor..span.range.start]);
+        out.push_str(replacement);
+        cursor = span.range.end;
+    }
+    out.push_str(&body[cursor..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_fences_and_urls_are_announced_not_read() {
+        let body = "Run it:\n```\ncargo test --workspace\n```\nDocs at https://example.com/guide now.";
+        let spans = find_skippable_spans(body);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].kind, SkipKind::CodeBlock);
+        assert_eq!(spans[1].kind, SkipKind::Url);
+        assert_eq!(
+            spoken_body(body, &spans, &SkipOptions::default()),
+            "Run it:\nCode block.\nDocs at link now."
+        );
+    }
+
+    #[test]
+    fn disabled_options_keep_the_raw_text() {
+        let body = "See www.example.com for `inline` info.";
+        let spans = find_skippable_spans(body);
+        let options = SkipOptions {
+            skip_code_blocks: true,
+            skip_urls: false,
+        };
+        assert_eq!(spoken_body(body, &spans, &options), body);
+    }
+
+    #[test]
+    fn urls_inside_code_blocks_collapse_into_the_block() {
+        let body = "```\ncurl https://example.com\n```";
+        let spans = find_skippable_spans(body);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].kind, SkipKind::CodeBlock);
+    }
+}
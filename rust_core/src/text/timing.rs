@@ -0,0 +1,387 @@
+//! Word-weight heuristics used to spread a sentence's audio duration
+//! across its words for highlighting.
+
+use std::time::Duration;
+
+/// Pacing knobs for the highlight loop, previously compile-time
+/// constants. Each can be overridden through a `VANILLA_READER_*_MS`
+/// environment variable; out-of-range values are clamped so a typo can't
+/// produce a busy-loop or a frozen highlight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingConfig {
+    /// Smallest interval between highlight advances; widening it reduces
+    /// wakeups on slow machines.
+    pub min_highlight_step: Duration,
+    /// Assumed per-word duration when the engine reports no usable audio
+    /// length.
+    pub fallback_word: Duration,
+    /// Granularity of the waiting sleep inside the highlight loop.
+    pub sleep_granularity: Duration,
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        Self {
+            min_highlight_step: Duration::from_millis(40),
+            fallback_word: Duration::from_millis(250),
+            sleep_granularity: Duration::from_millis(10),
+        }
+    }
+}
+
+impl TimingConfig {
+    pub const MIN_HIGHLIGHT_STEP_RANGE_MS: (u64, u64) = (10, 500);
+    pub const FALLBACK_WORD_RANGE_MS: (u64, u64) = (50, 2_000);
+    pub const SLEEP_GRANULARITY_RANGE_MS: (u64, u64) = (1, 100);
+
+    /// Defaults overridden by `VANILLA_READER_MIN_HIGHLIGHT_STEP_MS`,
+    /// `VANILLA_READER_FALLBACK_WORD_MS`, and
+    /// `VANILLA_READER_SLEEP_GRANULARITY_MS`.
+    pub fn from_environment() -> Self {
+        let defaults = Self::default();
+        Self {
+            min_highlight_step: env_duration(
+                "VANILLA_READER_MIN_HIGHLIGHT_STEP_MS",
+                Self::MIN_HIGHLIGHT_STEP_RANGE_MS,
+                defaults.min_highlight_step,
+            ),
+            fallback_word: env_duration(
+                "VANILLA_READER_FALLBACK_WORD_MS",
+                Self::FALLBACK_WORD_RANGE_MS,
+                defaults.fallback_word,
+            ),
+            sleep_granularity: env_duration(
+                "VANILLA_READER_SLEEP_GRANULARITY_MS",
+                Self::SLEEP_GRANULARITY_RANGE_MS,
+                defaults.sleep_granularity,
+            ),
+        }
+    }
+}
+
+/// Settings keys for the persisted timing knobs.
+const SETTING_MIN_HIGHLIGHT_STEP: &str = "timing.min_highlight_step_ms";
+const SETTING_FALLBACK_WORD: &str = "timing.fallback_word_ms";
+const SETTING_SLEEP_GRANULARITY: &str = "timing.sleep_granularity_ms";
+
+impl TimingConfig {
+    /// Load persisted timing settings, falling back to the environment
+    /// (and then the defaults) for anything unset. Stored values are
+    /// clamped to the same ranges as the env overrides, so a corrupt
+    /// row can't freeze the highlight.
+    pub fn from_settings(db: &crate::persistence::Database) -> Self {
+        let base = Self::from_environment();
+        let setting = |key: &str, range: (u64, u64), fallback: Duration| {
+            db.get_setting(key)
+                .ok()
+                .flatten()
+                .and_then(|value| value.parse::<u64>().ok())
+                .map(|ms| Duration::from_millis(ms.clamp(range.0, range.1)))
+                .unwrap_or(fallback)
+        };
+        Self {
+            min_highlight_step: setting(
+                SETTING_MIN_HIGHLIGHT_STEP,
+                Self::MIN_HIGHLIGHT_STEP_RANGE_MS,
+                base.min_highlight_step,
+            ),
+            fallback_word: setting(
+                SETTING_FALLBACK_WORD,
+                Self::FALLBACK_WORD_RANGE_MS,
+                base.fallback_word,
+            ),
+            sleep_granularity: setting(
+                SETTING_SLEEP_GRANULARITY,
+                Self::SLEEP_GRANULARITY_RANGE_MS,
+                base.sleep_granularity,
+            ),
+        }
+    }
+
+    /// Persist the current knobs so the calibrated highlight speed
+    /// survives restarts.
+    pub fn persist(
+        &self,
+        db: &crate::persistence::Database,
+    ) -> Result<(), crate::persistence::PersistenceError> {
+        db.set_setting(
+            SETTING_MIN_HIGHLIGHT_STEP,
+            &self.min_highlight_step.as_millis().to_string(),
+        )?;
+        db.set_setting(
+            SETTING_FALLBACK_WORD,
+            &self.fallback_word.as_millis().to_string(),
+        )?;
+        db.set_setting(
+            SETTING_SLEEP_GRANULARITY,
+            &self.sleep_granularity.as_millis().to_string(),
+        )
+    }
+}
+
+/// One sentence's worth of highlight-timing telemetry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentenceTiming {
+    pub sentence_index: usize,
+    /// Audio length the engine actually produced.
+    pub synthesized: Duration,
+    /// What the word-weight estimate predicted for the same sentence.
+    pub estimated: Duration,
+}
+
+impl SentenceTiming {
+    /// Signed estimate error: positive when the highlight would run
+    /// ahead of the audio.
+    pub fn drift(&self) -> i64 {
+        self.estimated.as_millis() as i64 - self.synthesized.as_millis() as i64
+    }
+}
+
+/// Opt-in per-sentence timing log for diagnosing highlight lead/lag on a
+/// given engine. Disabled (and free) unless `VANILLA_READER_TIMING_LOG`
+/// is set; set it to a path to also append CSV lines there, or to any
+/// other value for tracing output only.
+#[derive(Debug, Default)]
+pub struct TimingLog {
+    sink: Option<Option<std::path::PathBuf>>,
+}
+
+impl TimingLog {
+    pub const ENV_VAR: &'static str = "VANILLA_READER_TIMING_LOG";
+
+    pub fn from_environment() -> Self {
+        let sink = std::env::var(Self::ENV_VAR).ok().map(|value| {
+            let looks_like_path = value.contains('/') || value.contains('\\');
+            looks_like_path.then(|| std::path::PathBuf::from(value))
+        });
+        Self { sink }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// Record one sentence. A no-op when the log is disabled, so the
+    /// reading loop can call it unconditionally.
+    pub fn record(&self, timing: SentenceTiming) {
+        let Some(file) = &self.sink else {
+            return;
+        };
+        tracing::debug!(
+            target: "vanilla_reader::timing",
+            sentence = timing.sentence_index,
+            synthesized_ms = timing.synthesized.as_millis() as u64,
+            estimated_ms = timing.estimated.as_millis() as u64,
+            drift_ms = timing.drift(),
+            "sentence timing"
+        );
+        if let Some(path) = file {
+            use std::io::Write;
+            let line = format!(
+                "{},{},{},{}\n",
+                timing.sentence_index,
+                timing.synthesized.as_millis(),
+                timing.estimated.as_millis(),
+                timing.drift()
+            );
+            let _ = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut f| f.write_all(line.as_bytes()));
+        }
+    }
+}
+
+fn env_duration(var: &str, (min, max): (u64, u64), default: Duration) -> Duration {
+    match std::env::var(var).ok().and_then(|v| v.parse::<u64>().ok()) {
+        Some(ms) => Duration::from_millis(ms.clamp(min, max)),
+        None => default,
+    }
+}
+
+/// How much of a sentence's duration each word should get.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WordWeighting {
+    /// Weight by alphanumeric character count.
+    CharCount,
+    /// Weight by an estimated syllable count, which tracks speech cadence
+    /// better than raw length (compounds stop dominating, silent letters
+    /// stop inflating). Falls back to character count per word for scripts
+    /// where the vowel-group heuristic doesn't apply.
+    #[default]
+    Syllables,
+}
+
+/// Relative weights for the words of a sentence; every word gets at least
+/// a small positive weight so punctuation-only tokens still advance.
+pub fn compute_word_weights(words: &[&str], weighting: WordWeighting) -> Vec<f32> {
+    words
+        .iter()
+        .map(|word| {
+            let chars = word.chars().filter(|c| c.is_alphanumeric()).count();
+            let weight = match weighting {
+                WordWeighting::CharCount => chars,
+                WordWeighting::Syllables => estimate_syllables(word).unwrap_or(chars),
+            };
+            (weight as f32).max(1.0)
+        })
+        .collect()
+}
+
+/// Rescale the not-yet-spoken word durations when the rate changes
+/// mid-sentence, so the highlight cadence follows the slider
+/// immediately instead of waiting for the next sentence. Words before
+/// `next_word` already played at the old rate and are left alone.
+/// Non-positive rates are ignored (the slider can't produce them; a
+/// corrupt setting shouldn't zero the cadence).
+pub fn rescale_remaining_word_durations(
+    durations: &mut [Duration],
+    next_word: usize,
+    old_rate: f32,
+    new_rate: f32,
+) {
+    if old_rate <= 0.0 || new_rate <= 0.0 || old_rate == new_rate {
+        return;
+    }
+    let factor = f64::from(old_rate) / f64::from(new_rate);
+    for duration in durations.iter_mut().skip(next_word) {
+        *duration = duration.mul_f64(factor);
+    }
+}
+
+/// Vowel-group syllable estimate for Latin-script words: each run of
+/// vowels counts once, a trailing silent "e" is dropped. Returns `None`
+/// for words without ASCII letters (numbers, CJK, symbols) so callers can
+/// fall back to character count.
+pub fn estimate_syllables(word: &str) -> Option<usize> {
+    let lower: String = word
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if lower.is_empty() {
+        return None;
+    }
+
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut syllables = 0usize;
+    let mut in_group = false;
+    for c in lower.chars() {
+        if is_vowel(c) {
+            if !in_group {
+                syllables += 1;
+                in_group = true;
+            }
+        } else {
+            in_group = false;
+        }
+    }
+    // Trailing silent e ("make", "whole") doesn't add a syllable, but a
+    // consonant-le ending ("table", "little") keeps it.
+    if syllables > 1 && lower.ends_with('e') {
+        let chars: Vec<char> = lower.chars().collect();
+        let consonant_le = chars.len() >= 3
+            && chars[chars.len() - 2] == 'l'
+            && !is_vowel(chars[chars.len() - 3]);
+        if !consonant_le {
+            syllables -= 1;
+        }
+    }
+    Some(syllables.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_overrides_are_clamped() {
+        std::env::set_var("VANILLA_READER_MIN_HIGHLIGHT_STEP_MS", "3");
+        std::env::set_var("VANILLA_READER_FALLBACK_WORD_MS", "400");
+        let config = TimingConfig::from_environment();
+        std::env::remove_var("VANILLA_READER_MIN_HIGHLIGHT_STEP_MS");
+        std::env::remove_var("VANILLA_READER_FALLBACK_WORD_MS");
+        assert_eq!(config.min_highlight_step, Duration::from_millis(10));
+        assert_eq!(config.fallback_word, Duration::from_millis(400));
+        assert_eq!(config.sleep_granularity, TimingConfig::default().sleep_granularity);
+    }
+
+    #[test]
+    fn persisted_timing_settings_round_trip_with_clamping() {
+        let db = crate::persistence::Database::open_in_memory().unwrap();
+        let config = TimingConfig {
+            min_highlight_step: Duration::from_millis(80),
+            fallback_word: Duration::from_millis(320),
+            sleep_granularity: TimingConfig::default().sleep_granularity,
+        };
+        config.persist(&db).unwrap();
+        assert_eq!(TimingConfig::from_settings(&db), config);
+
+        // Out-of-range stored values clamp instead of being trusted.
+        db.set_setting("timing.fallback_word_ms", "999999").unwrap();
+        assert_eq!(
+            TimingConfig::from_settings(&db).fallback_word,
+            Duration::from_millis(TimingConfig::FALLBACK_WORD_RANGE_MS.1)
+        );
+    }
+
+    #[test]
+    fn timing_log_stays_disabled_without_the_env_var() {
+        std::env::remove_var(TimingLog::ENV_VAR);
+        let log = TimingLog::from_environment();
+        assert!(!log.is_enabled());
+        // Recording while disabled is a no-op, not an error.
+        log.record(SentenceTiming {
+            sentence_index: 0,
+            synthesized: Duration::from_millis(900),
+            estimated: Duration::from_millis(750),
+        });
+    }
+
+    #[test]
+    fn drift_is_signed_toward_the_estimate() {
+        let timing = SentenceTiming {
+            sentence_index: 3,
+            synthesized: Duration::from_millis(900),
+            estimated: Duration::from_millis(750),
+        };
+        assert_eq!(timing.drift(), -150);
+    }
+
+    #[test]
+    fn syllable_estimates_track_cadence() {
+        assert_eq!(estimate_syllables("cat"), Some(1));
+        assert_eq!(estimate_syllables("reading"), Some(2));
+        assert_eq!(estimate_syllables("whole"), Some(1));
+        assert_eq!(estimate_syllables("table"), Some(2));
+        assert_eq!(estimate_syllables("1999"), None);
+    }
+
+    #[test]
+    fn rate_changes_rescale_only_the_unspoken_words() {
+        let mut durations = vec![Duration::from_millis(200); 4];
+        // Doubling the rate halves what's left; words 0–1 already played.
+        rescale_remaining_word_durations(&mut durations, 2, 1.0, 2.0);
+        assert_eq!(durations[0], Duration::from_millis(200));
+        assert_eq!(durations[1], Duration::from_millis(200));
+        assert_eq!(durations[2], Duration::from_millis(100));
+        assert_eq!(durations[3], Duration::from_millis(100));
+
+        // Nonsense rates leave the cadence untouched.
+        rescale_remaining_word_durations(&mut durations, 0, 0.0, 2.0);
+        rescale_remaining_word_durations(&mut durations, 0, 1.0, -1.0);
+        assert_eq!(durations[3], Duration::from_millis(100));
+    }
+
+    #[test]
+    fn weights_fall_back_to_char_count_for_non_latin() {
+        let words = ["strengths", "読書", "a"];
+        let weights = compute_word_weights(&words, WordWeighting::Syllables);
+        // One syllable despite nine letters.
+        assert_eq!(weights[0], 1.0);
+        // No ASCII letters: falls back to character count.
+        assert_eq!(weights[1], 2.0);
+        assert_eq!(weights[2], 1.0);
+    }
+}
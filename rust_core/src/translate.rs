@@ -0,0 +1,168 @@
+//! Translation of selected text through a configurable backend.
+//!
+//! The core owns the selection plumbing, configuration, and result
+//! shape; the backend — an offline model or an online API — is a trait
+//! the frontend registers, mirroring how PDF rendering and Wikipedia
+//! lookup inject their heavy dependencies. Only compiled in behind the
+//! `translation` feature.
+
+const SETTING_BACKEND: &str = "translate.backend";
+const SETTING_TARGET: &str = "translate.target";
+const SETTING_API_KEY: &str = "translate.api_key";
+
+/// A translation provider. `translate` receives BCP 47 language tags;
+/// `source` is `None` when nothing upstream detected the selection's
+/// language and the backend should detect it itself.
+pub trait TranslationBackend: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn translate(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<String, String>;
+}
+
+/// A finished translation, shown alongside the original. The target
+/// language tag is kept so the caller can pick a matching voice when
+/// the user asks to hear it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translation {
+    pub original: String,
+    pub translated: String,
+    pub source_language: Option<String>,
+    pub target_language: String,
+}
+
+/// Translate the selected `text` into `target`. Empty selections are
+/// rejected before reaching the backend; backend errors (missing API
+/// key, offline, unsupported pair) pass through for the popup.
+pub fn translate_selection(
+    backend: &dyn TranslationBackend,
+    text: &str,
+    source: Option<&str>,
+    target: &str,
+) -> Result<Translation, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("nothing selected".to_string());
+    }
+    let translated = backend.translate(text, source, target)?;
+    Ok(Translation {
+        original: text.to_string(),
+        translated,
+        source_language: source.map(str::to_string),
+        target_language: target.to_string(),
+    })
+}
+
+/// Which backend to use and where to translate to, persisted with the
+/// rest of the application settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslationConfig {
+    /// Matches a registered backend's [`TranslationBackend::name`].
+    pub backend: String,
+    /// Target language tag, e.g. "en" or "de".
+    pub target: String,
+    /// Key for online backends; offline backends ignore it.
+    pub api_key: Option<String>,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            backend: String::new(),
+            target: "en".to_string(),
+            api_key: None,
+        }
+    }
+}
+
+impl TranslationConfig {
+    pub fn from_settings(db: &crate::persistence::Database) -> Self {
+        let base = Self::default();
+        let setting = |key: &str| db.get_setting(key).ok().flatten();
+        Self {
+            backend: setting(SETTING_BACKEND).unwrap_or(base.backend),
+            target: setting(SETTING_TARGET).unwrap_or(base.target),
+            api_key: setting(SETTING_API_KEY),
+        }
+    }
+
+    pub fn persist(
+        &self,
+        db: &crate::persistence::Database,
+    ) -> Result<(), crate::persistence::PersistenceError> {
+        db.set_setting(SETTING_BACKEND, &self.backend)?;
+        db.set_setting(SETTING_TARGET, &self.target)?;
+        // A removed key must leave the table: it's a credential, and a
+        // stale row would quietly keep authenticating requests.
+        match &self.api_key {
+            Some(key) => db.set_setting(SETTING_API_KEY, key)?,
+            None => db.delete_setting(SETTING_API_KEY)?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UpperBackend;
+
+    impl TranslationBackend for UpperBackend {
+        fn name(&self) -> &str {
+            "upper"
+        }
+
+        fn translate(
+            &self,
+            text: &str,
+            source: Option<&str>,
+            target: &str,
+        ) -> Result<String, String> {
+            if source == Some("xx") {
+                return Err("unsupported language pair".to_string());
+            }
+            Ok(format!("[{target}] {}", text.to_uppercase()))
+        }
+    }
+
+    #[test]
+    fn selections_translate_with_language_tags_attached() {
+        let translation =
+            translate_selection(&UpperBackend, "  bonjour  ", Some("fr"), "en").unwrap();
+        assert_eq!(translation.original, "bonjour");
+        assert_eq!(translation.translated, "[en] BONJOUR");
+        assert_eq!(translation.source_language.as_deref(), Some("fr"));
+        assert_eq!(translation.target_language, "en");
+
+        assert_eq!(
+            translate_selection(&UpperBackend, "   ", None, "en"),
+            Err("nothing selected".to_string())
+        );
+        assert!(translate_selection(&UpperBackend, "hi", Some("xx"), "en").is_err());
+    }
+
+    #[test]
+    fn config_round_trips_through_settings() {
+        let db = crate::persistence::Database::open_in_memory().unwrap();
+        assert_eq!(TranslationConfig::from_settings(&db), TranslationConfig::default());
+
+        let mut config = TranslationConfig {
+            backend: "upper".to_string(),
+            target: "de".to_string(),
+            api_key: Some("k-123".to_string()),
+        };
+        config.persist(&db).unwrap();
+        assert_eq!(TranslationConfig::from_settings(&db), config);
+
+        // Removing the API key deletes the stored credential.
+        config.api_key = None;
+        config.persist(&db).unwrap();
+        assert_eq!(TranslationConfig::from_settings(&db).api_key, None);
+        assert_eq!(db.get_setting(SETTING_API_KEY).unwrap(), None);
+    }
+}